@@ -1,12 +1,15 @@
 #![allow(dead_code)]
 
 use std::collections::HashMap;
+use std::io::{self, BufRead};
+
+use crate::utils::parse::{invalid_data, open_input, read_records};
 
 const INPUT_PATH: &str = "src/inputs/day_1.txt";
 // const INPUT_PATH: &str = "src/inputs/day_1_test.txt";
 // const INPUT_PATH: &str = "src/inputs/day_1_test_2.txt";
 
-const LETTERS_AS_DIGITS: [(&str, u32); 9] = [
+pub(crate) const LETTERS_AS_DIGITS: [(&str, u32); 9] = [
     ("one", 1),
     ("two", 2),
     ("three", 3),
@@ -19,71 +22,68 @@ const LETTERS_AS_DIGITS: [(&str, u32); 9] = [
 ];
 
 pub fn day_1_part_1() {
-    let input = std::fs::read_to_string(INPUT_PATH).unwrap();
-    let mut digits: Vec<u32> = Vec::new();
-    for line in input.lines() {
-        let digits_in_number = get_digits_in_number(line);
-        digits.push(digits_in_number);
-    }
-
-    let mut sum: u32 = 0;
-    for digit in digits {
-        sum += digit;
-    }
-
+    let sum = sum_calibration_values(open_input(INPUT_PATH).unwrap()).unwrap();
     println!("Sum: {}", sum);
 }
 
 pub fn day_1_part_2() {
-    let input = std::fs::read_to_string(INPUT_PATH).unwrap();
-    let mut digits: Vec<u32> = Vec::new();
-    for line in input.lines() {
-        let split_line: Vec<String> = split_into_digits_and_letters(line);
-        let mut digits_in_string: Vec<u32> = Vec::new();
-        for part in split_line {
-            if part.chars().all(char::is_numeric) {
-                let digit = part.parse::<u32>().unwrap();
-                digits_in_string.push(digit);
-            } else {
-                let mut letters_as_digits = get_letters_as_digits(&part);
-                digits_in_string.append(&mut letters_as_digits);
-            }
-        }
-        let first_digit = digits_in_string[0];
-        let last_digit = digits_in_string[digits_in_string.len() - 1];
-        let full_digit = first_digit * 10 + last_digit;
-        digits.push(full_digit);
+    let sum = sum_calibration_values_with_letters(open_input(INPUT_PATH).unwrap()).unwrap();
+    println!("Sum: {}", sum);
+}
+
+/// Sums the calibration value of each line, reading one line at a time.
+pub fn sum_calibration_values<R: BufRead>(reader: R) -> io::Result<u64> {
+    let mut sum: u64 = 0;
+    for line in read_records(reader) {
+        let line = line?;
+        sum += get_digits_in_number(&line).ok_or_else(|| invalid_data(format!("Line has no digit: {}", line)))? as u64;
     }
 
-    let mut sum: u32 = 0;
-    for digit in digits {
-        sum += digit;
+    Ok(sum)
+}
+
+/// Same as `sum_calibration_values`, with digits also spelled out with letters.
+pub fn sum_calibration_values_with_letters<R: BufRead>(reader: R) -> io::Result<u64> {
+    let mut sum: u64 = 0;
+    for line in read_records(reader) {
+        let line = line?;
+        sum += get_digits_with_letters(&line).ok_or_else(|| invalid_data(format!("Line has no digit: {}", line)))? as u64;
     }
 
-    println!("Sum: {}", sum);
+    Ok(sum)
 }
 
-fn get_digit(input: &str, pattern: &str) -> u32 {
-    let re = regex::Regex::new(pattern).unwrap();
-    let caps = re.captures(input).unwrap();
-    let digit = caps.name("digit").unwrap().as_str().parse::<u32>().unwrap();
-    digit
+fn get_first_digit(input: &str) -> Option<u32> {
+    input.chars().find_map(|x| x.to_digit(10))
 }
 
-fn get_first_digit(input: &str) -> u32 {
-    get_digit(input, r"(?P<digit>\d).*")
+fn get_last_digit(input: &str) -> Option<u32> {
+    input.chars().rev().find_map(|x| x.to_digit(10))
 }
 
-fn get_last_digit(input: &str) -> u32 {
-    get_digit(input, r".*(?P<digit>\d)")
+fn get_digits_in_number(input: &str) -> Option<u32> {
+    let first_digit = get_first_digit(input)?;
+    let last_digit = get_last_digit(input)?;
+
+    Some(first_digit * 10 + last_digit)
 }
 
-fn get_digits_in_number(input: &str) -> u32 {
-    let first_digit = get_first_digit(input);
-    let last_digit = get_last_digit(input);
-    let digits_in_number = first_digit * 10 + last_digit;
+fn get_digits_with_letters(input: &str) -> Option<u32> {
+    let split_line: Vec<String> = split_into_digits_and_letters(input);
+    let mut digits_in_string: Vec<u32> = Vec::new();
+    for part in split_line {
+        if part.chars().all(|x| x.is_ascii_digit()) {
+            let digit = part.parse::<u32>().unwrap();
+            digits_in_string.push(digit);
+        } else {
+            let mut letters_as_digits = get_letters_as_digits(&part);
+            digits_in_string.append(&mut letters_as_digits);
+        }
+    }
+    let first_digit = digits_in_string.first()?;
+    let last_digit = digits_in_string.last()?;
 
-    digits_in_number
+    Some(first_digit * 10 + last_digit)
 }
 
 fn get_letters_as_digits(input: &str) -> Vec<u32> {
@@ -92,7 +92,7 @@ fn get_letters_as_digits(input: &str) -> Vec<u32> {
 
     // example: two1nine -> [2,9], eightwothree -> [8,2,3], abcone2threexyz -> [1,2], sgjshgjs -> []
     let mut input = input;
-    while input.len() > 0 {
+    while !input.is_empty() {
         let mut found_digit = false;
         for (letter, digit) in &letters_as_digits {
             if input.starts_with(letter) {
@@ -103,7 +103,9 @@ fn get_letters_as_digits(input: &str) -> Vec<u32> {
             }
         }
         if !found_digit {
-            input = &input[1..];
+            let mut rest = input.chars();
+            rest.next();
+            input = rest.as_str();
         }
     }
 
@@ -113,7 +115,7 @@ fn get_letters_as_digits(input: &str) -> Vec<u32> {
 fn split_into_digits_and_letters(input: &str) -> Vec<String> {
     let mut result = Vec::new();
     let mut index = 0;
-    let chars: Vec<char> = input.chars().collect();
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
 
     while index < chars.len() {
         let mut longest_match = "";
@@ -121,7 +123,7 @@ fn split_into_digits_and_letters(input: &str) -> Vec<String> {
 
         // Chercher le plus long mot correspondant
         for &(word, _) in &LETTERS_AS_DIGITS {
-            if input[index..].starts_with(word) && word.len() > match_len {
+            if input[chars[index].0..].starts_with(word) && word.len() > match_len {
                 longest_match = word;
                 match_len = word.len();
             }
@@ -132,7 +134,7 @@ fn split_into_digits_and_letters(input: &str) -> Vec<String> {
             result.push(longest_match.to_string());
         } else {
             // Traiter comme un caractère ordinaire
-            result.push(chars[index].to_string());
+            result.push(chars[index].1.to_string());
         }
 
         // Incrémenter l'index de 1
@@ -140,4 +142,40 @@ fn split_into_digits_and_letters(input: &str) -> Vec<String> {
     }
 
     result
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        1abc2
+        pqr3stu8vwx
+        a1b2c3d4e5f
+        treb7uchet
+    */
+
+    #[test]
+    fn test_sum_calibration_values() {
+        let input = include_str!("../../src/inputs/day_1_test.txt");
+
+        assert_eq!(sum_calibration_values(input.as_bytes()).unwrap(), 142);
+    }
+
+    #[test]
+    fn test_sum_calibration_values_with_letters() {
+        let input = include_str!("../../src/inputs/day_1_test_2.txt");
+
+        assert_eq!(sum_calibration_values_with_letters(input.as_bytes()).unwrap(), 281);
+    }
+
+    #[test]
+    fn test_line_without_digit() {
+        let error = sum_calibration_values("1abc2\nabc\n".as_bytes()).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "Line has no digit: abc");
+        assert!(sum_calibration_values_with_letters("xyz".as_bytes()).is_err());
+        assert_eq!(sum_calibration_values_with_letters("x½one".as_bytes()).unwrap(), 11);
+    }
+}
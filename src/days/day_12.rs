@@ -0,0 +1,271 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_12_test.txt"),
+        false => include_str!("../../src/inputs/day_12.txt")
+    }
+}
+
+pub const UNFOLD_FACTOR: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl TryFrom<char> for Spring {
+    type Error = Box<dyn Error>;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Spring::Operational),
+            '#' => Ok(Spring::Damaged),
+            '?' => Ok(Spring::Unknown),
+            _ => Err(format!("Unknown spring condition: {}", c).into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    springs: Vec<Spring>,
+    groups: Vec<usize>,
+}
+
+impl std::str::FromStr for Record {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (springs, groups) = s.trim()
+            .split_once(' ')
+            .ok_or("Record must have springs and groups separated by a space")?;
+
+        let springs = springs.chars()
+            .map(Spring::try_from)
+            .collect::<Result<Vec<Spring>, _>>()?;
+
+        let groups = groups.split(',')
+            .map(|x| x.parse::<usize>())
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        Ok(Record {
+            springs,
+            groups,
+        })
+    }
+}
+
+impl Record {
+    /// Repeats the springs `factor` times joined by an unknown spring, and the groups `factor` times.
+    pub fn unfold(&self, factor: usize) -> Record {
+        let mut springs: Vec<Spring> = Vec::new();
+        for i in 0..factor {
+            if i > 0 {
+                springs.push(Spring::Unknown);
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+
+        Record {
+            springs,
+            groups: self.groups.repeat(factor),
+        }
+    }
+
+    /// Counts the ways the unknown springs can be filled so the damaged runs match the groups.
+    fn count_arrangements(&self) -> u64 {
+        let mut memo: Vec<Vec<Option<u64>>> = vec![vec![None; self.groups.len() + 1]; self.springs.len() + 1];
+        self.count_from(0, 0, &mut memo)
+    }
+
+    fn count_from(&self, spring: usize, group: usize, memo: &mut Vec<Vec<Option<u64>>>) -> u64 {
+        if let Some(count) = memo[spring][group] {
+            return count;
+        }
+
+        let count = if spring == self.springs.len() {
+            (group == self.groups.len()) as u64
+        } else {
+            let mut count: u64 = 0;
+
+            // The spring is operational: move on to the next one
+            if self.springs[spring] != Spring::Damaged {
+                count = self.count_from(spring + 1, group, memo);
+            }
+
+            // The spring starts the next damaged group
+            if self.springs[spring] != Spring::Operational && self.fits_group(spring, group) {
+                let next = (spring + self.groups[group] + 1).min(self.springs.len());
+                count = count
+                    .checked_add(self.count_from(next, group + 1, memo))
+                    .expect("Error: arrangement count overflowed u64");
+            }
+
+            count
+        };
+
+        memo[spring][group] = Some(count);
+        count
+    }
+
+    /// Whether the group can be placed starting at `spring`, followed by an end or a non-damaged spring.
+    fn fits_group(&self, spring: usize, group: usize) -> bool {
+        let Some(&length) = self.groups.get(group) else {
+            return false;
+        };
+
+        let end = spring + length;
+        if end > self.springs.len() {
+            return false;
+        }
+
+        let all_may_be_damaged = self.springs[spring..end].iter().all(|x| *x != Spring::Operational);
+        let is_closed = end == self.springs.len() || self.springs[end] != Spring::Damaged;
+
+        all_may_be_damaged && is_closed
+    }
+}
+
+pub fn parse_records(input: &str) -> Result<Vec<Record>, Box<dyn Error>> {
+    input
+        .lines()
+        .filter(|x| !x.trim().is_empty())
+        .map(|x| x.parse::<Record>())
+        .collect::<Result<Vec<Record>, _>>()
+}
+
+pub fn sum_arrangements(records: &[Record]) -> u64 {
+    records.iter().fold(0, |acc: u64, record| {
+        acc.checked_add(record.count_arrangements())
+            .expect("Error: sum of arrangements overflowed u64")
+    })
+}
+
+pub fn day_12_part_1() {
+    let records = parse_records(get_input(false)).unwrap();
+    println!("Sum of arrangements: {}", sum_arrangements(&records));
+}
+
+pub fn day_12_part_2() {
+    let records = parse_records(get_input(false)).unwrap()
+        .iter()
+        .map(|x| x.unfold(UNFOLD_FACTOR))
+        .collect::<Vec<Record>>();
+    println!("Sum of unfolded arrangements: {}", sum_arrangements(&records));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        ???.### 1,1,3
+        .??..??...?##. 1,1,3
+        ?#?#?#?#?#?#?#? 1,3,1,6
+        ????.#...#... 4,1,1
+        ????.######..#####. 1,6,5
+        ?###???????? 3,2,1
+    */
+
+    /// Tries every assignment of the unknown springs and keeps the ones matching the groups.
+    fn count_arrangements_brute_force(record: &Record) -> u64 {
+        let unknowns = record.springs.iter()
+            .enumerate()
+            .filter(|(_, x)| **x == Spring::Unknown)
+            .map(|(i, _)| i)
+            .collect::<Vec<usize>>();
+
+        let mut count = 0;
+        for mask in 0..(1_u64 << unknowns.len()) {
+            let mut springs = record.springs.clone();
+            for (bit, index) in unknowns.iter().enumerate() {
+                springs[*index] = match mask >> bit & 1 {
+                    1 => Spring::Damaged,
+                    _ => Spring::Operational,
+                };
+            }
+
+            let groups = springs
+                .split(|x| *x == Spring::Operational)
+                .map(|x| x.len())
+                .filter(|x| *x > 0)
+                .collect::<Vec<usize>>();
+
+            if groups == record.groups {
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    #[test]
+    fn test_parse() {
+        let records = parse_records(get_input(true)).unwrap();
+
+        assert_eq!(records.len(), 6);
+        assert_eq!(records[0].springs[0], Spring::Unknown);
+        assert_eq!(records[0].springs[3], Spring::Operational);
+        assert_eq!(records[0].springs[4], Spring::Damaged);
+        assert_eq!(records[0].groups, vec![1, 1, 3]);
+    }
+
+    #[test]
+    fn test_count_arrangements() {
+        let records = parse_records(get_input(true)).unwrap();
+        let counts = records.iter().map(|x| x.count_arrangements()).collect::<Vec<u64>>();
+
+        assert_eq!(counts, vec![1, 4, 1, 1, 4, 10]);
+        assert_eq!(sum_arrangements(&records), 21);
+    }
+
+    #[test]
+    fn test_count_unfolded_arrangements() {
+        let records = parse_records(get_input(true)).unwrap()
+            .iter()
+            .map(|x| x.unfold(UNFOLD_FACTOR))
+            .collect::<Vec<Record>>();
+        let counts = records.iter().map(|x| x.count_arrangements()).collect::<Vec<u64>>();
+
+        assert_eq!(counts, vec![1, 16384, 1, 16, 2500, 506250]);
+        assert_eq!(sum_arrangements(&records), 525152);
+    }
+
+    #[test]
+    fn test_unfold() {
+        let record = ".# 1".parse::<Record>().unwrap().unfold(UNFOLD_FACTOR);
+
+        assert_eq!(record.springs.len(), 14);
+        assert_eq!(record.springs[2], Spring::Unknown);
+        assert_eq!(record.groups, vec![1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut records = parse_records(get_input(true)).unwrap();
+        records.push("?????????? 1,1".parse::<Record>().unwrap());
+        records.push("#?#?#?#? 2,3".parse::<Record>().unwrap());
+        records.push("???? 5".parse::<Record>().unwrap());
+        records.push(".... 1".parse::<Record>().unwrap());
+        records.push("???.?? 1".parse::<Record>().unwrap());
+        records.push(records[0].unfold(2));
+        records.push(records[1].unfold(2));
+
+        for record in records.iter() {
+            assert_eq!(record.count_arrangements(), count_arrangements_brute_force(record));
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!("??x 1".parse::<Record>().is_err());
+        assert!("??? 1,a".parse::<Record>().is_err());
+        assert!("???".parse::<Record>().is_err());
+        assert!(parse_records("???.### 1,1,3\n???.### 1,x").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+
+use crate::utils::parse::split_blocks;
+
+pub(crate) fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_13_test.txt"),
+        false => include_str!("../../src/inputs/day_13.txt")
+    }
+}
+
+const ROW_FACTOR: usize = 100;
+
+#[derive(Debug, PartialEq)]
+enum Reflection {
+    Vertical(usize),
+    Horizontal(usize),
+}
+
+impl Reflection {
+    fn summary(&self) -> usize {
+        match self {
+            Reflection::Vertical(columns) => *columns,
+            Reflection::Horizontal(rows) => rows * ROW_FACTOR,
+        }
+    }
+}
+
+/// A pattern stored as bitmasks, one per row and one per column, where a set bit is a rock (`#`).
+#[derive(Debug)]
+pub struct Pattern {
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+impl std::str::FromStr for Pattern {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s.lines().map(|x| x.trim()).collect::<Vec<&str>>();
+        let width = lines.first().ok_or("Pattern is empty")?.len();
+
+        if width > 64 || lines.len() > 64 {
+            return Err("Pattern is too large to fit in 64 bits".into());
+        }
+
+        let mut rows: Vec<u64> = vec![0; lines.len()];
+        let mut columns: Vec<u64> = vec![0; width];
+        for (y, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                return Err(format!("Line {} has not the same width as the first one", y + 1).into());
+            }
+
+            for (x, character) in line.chars().enumerate() {
+                match character {
+                    '#' => {
+                        rows[y] |= 1 << x;
+                        columns[x] |= 1 << y;
+                    }
+                    '.' => {}
+                    _ => return Err(format!("Unknown character in pattern: {}", character).into()),
+                }
+            }
+        }
+
+        Ok(Pattern {
+            rows,
+            columns,
+        })
+    }
+}
+
+impl Pattern {
+    /// Finds the reflection line where exactly `smudges` cells differ from their mirrored cell.
+    fn find_reflection(&self, smudges: u32) -> Option<Reflection> {
+        if let Some(columns) = find_mirror(&self.columns, smudges) {
+            return Some(Reflection::Vertical(columns));
+        }
+
+        find_mirror(&self.rows, smudges).map(Reflection::Horizontal)
+    }
+}
+
+/// Returns the number of lines before the mirror, for the first mirror with exactly `smudges` mismatches.
+fn find_mirror(lines: &[u64], smudges: u32) -> Option<usize> {
+    (1..lines.len()).find(|&mirror| {
+        let mismatches: u32 = lines[..mirror]
+            .iter()
+            .rev()
+            .zip(lines[mirror..].iter())
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum();
+
+        mismatches == smudges
+    })
+}
+
+pub fn parse_patterns(input: &str) -> Result<Vec<Pattern>, Box<dyn Error>> {
+    split_blocks(input)
+        .iter()
+        .map(|x| x.parse::<Pattern>())
+        .collect::<Result<Vec<Pattern>, _>>()
+}
+
+pub fn summarize(patterns: &[Pattern], smudges: u32) -> Result<usize, Box<dyn Error>> {
+    patterns
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let reflection = x.find_reflection(smudges)
+                .ok_or(format!("Pattern {} has no reflection with {} smudges", i + 1, smudges))?;
+            Ok(reflection.summary())
+        })
+        .sum()
+}
+
+pub fn day_13_part_1() {
+    let patterns = parse_patterns(get_input(false)).unwrap();
+    println!("Summary: {}", summarize(&patterns, 0).unwrap());
+}
+
+pub fn day_13_part_2() {
+    let patterns = parse_patterns(get_input(false)).unwrap();
+    println!("Summary with smudges: {}", summarize(&patterns, 1).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        #.##..##.
+        ..#.##.#.
+        ##......#
+        ##......#
+        ..#.##.#.
+        ..##..##.
+        #.#.##.#.
+
+        #...##..#
+        #....#..#
+        ..##..###
+        #####.##.
+        #####.##.
+        ..##..###
+        #....#..#
+    */
+
+    #[test]
+    fn test_parse() {
+        let patterns = parse_patterns(get_input(true)).unwrap();
+
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(patterns[0].rows.len(), 7);
+        assert_eq!(patterns[0].columns.len(), 9);
+        assert_eq!(patterns[0].rows[0], 0b011001101);
+        assert_eq!(patterns[0].columns[0], 0b1001101);
+    }
+
+    #[test]
+    fn test_find_reflection() {
+        let patterns = parse_patterns(get_input(true)).unwrap();
+
+        assert_eq!(patterns[0].find_reflection(0), Some(Reflection::Vertical(5)));
+        assert_eq!(patterns[1].find_reflection(0), Some(Reflection::Horizontal(4)));
+        assert_eq!(summarize(&patterns, 0).unwrap(), 405);
+    }
+
+    #[test]
+    fn test_find_reflection_with_smudge() {
+        let patterns = parse_patterns(get_input(true)).unwrap();
+
+        assert_eq!(patterns[0].find_reflection(1), Some(Reflection::Horizontal(3)));
+        assert_eq!(patterns[1].find_reflection(1), Some(Reflection::Horizontal(1)));
+        assert_eq!(summarize(&patterns, 1).unwrap(), 400);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!("#.\n#".parse::<Pattern>().is_err());
+        assert!("#x".parse::<Pattern>().is_err());
+        assert!(parse_patterns("#.\n.#\n\n#x").is_err());
+    }
+
+    #[test]
+    fn test_no_reflection() {
+        let patterns = parse_patterns("#.\n..\n\n#.#\n##.").unwrap();
+
+        assert!(summarize(&patterns, 0).is_err());
+        assert!(summarize(&patterns[..1], 1).is_ok());
+    }
+}
@@ -0,0 +1,209 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+
+use crate::utils::cycle::CycleDetector;
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_14_test.txt"),
+        false => include_str!("../../src/inputs/day_14.txt")
+    }
+}
+
+pub const SPIN_CYCLES: usize = 1_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Rock {
+    Round,
+    Cube,
+    Empty,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Direction {
+    North,
+    West,
+    South,
+    East,
+}
+
+const SPIN: [Direction; 4] = [Direction::North, Direction::West, Direction::South, Direction::East];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    rocks: Vec<Vec<Rock>>,
+}
+
+impl std::str::FromStr for Platform {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rocks = s.lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .map(|line| line.chars().map(|c| match c {
+                'O' => Ok(Rock::Round),
+                '#' => Ok(Rock::Cube),
+                '.' => Ok(Rock::Empty),
+                _ => Err(format!("Unknown rock: {}", c)),
+            }).collect::<Result<Vec<Rock>, String>>())
+            .collect::<Result<Vec<Vec<Rock>>, String>>()?;
+
+        if rocks.iter().any(|x| x.len() != rocks[0].len()) {
+            return Err("All the lines of the platform must have the same width".into());
+        }
+
+        Ok(Platform {
+            rocks,
+        })
+    }
+}
+
+impl Platform {
+    fn height(&self) -> usize {
+        self.rocks.len()
+    }
+
+    fn width(&self) -> usize {
+        self.rocks.first().map_or(0, |x| x.len())
+    }
+
+    /// Lists the lines the rocks roll along, each one ordered from the edge the rocks roll towards.
+    fn lanes(&self, direction: Direction) -> Vec<Vec<(usize, usize)>> {
+        let (width, height) = (self.width(), self.height());
+
+        match direction {
+            Direction::North => (0..width).map(|x| (0..height).map(|y| (x, y)).collect()).collect(),
+            Direction::South => (0..width).map(|x| (0..height).rev().map(|y| (x, y)).collect()).collect(),
+            Direction::West => (0..height).map(|y| (0..width).map(|x| (x, y)).collect()).collect(),
+            Direction::East => (0..height).map(|y| (0..width).rev().map(|x| (x, y)).collect()).collect(),
+        }
+    }
+
+    pub fn tilt(&mut self, direction: Direction) {
+        for lane in self.lanes(direction) {
+            let mut free = 0;
+            for (i, &(x, y)) in lane.iter().enumerate() {
+                match self.rocks[y][x] {
+                    Rock::Cube => free = i + 1,
+                    Rock::Round => {
+                        let (free_x, free_y) = lane[free];
+                        self.rocks[y][x] = Rock::Empty;
+                        self.rocks[free_y][free_x] = Rock::Round;
+                        free += 1;
+                    }
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+
+    fn spin(&mut self) {
+        for direction in SPIN {
+            self.tilt(direction);
+        }
+    }
+
+    /// Each round rock weighs as much as its distance to the south edge.
+    pub fn north_load(&self) -> usize {
+        self.rocks
+            .iter()
+            .enumerate()
+            .map(|(y, row)| row.iter().filter(|x| **x == Rock::Round).count() * (self.height() - y))
+            .sum()
+    }
+
+    /// Spins the platform `cycles` times, skipping ahead as soon as a state repeats.
+    pub fn load_after_spins(&self, cycles: usize) -> usize {
+        let mut platform = self.clone();
+        let mut detector = CycleDetector::new();
+        let mut loads: Vec<usize> = Vec::new();
+
+        for index in 0..=cycles {
+            loads.push(platform.north_load());
+
+            if let Some(cycle) = detector.record(platform.clone(), index) {
+                return loads[cycle.equivalent_index(cycles)];
+            }
+
+            platform.spin();
+        }
+
+        loads[cycles]
+    }
+}
+
+pub fn day_14_part_1() {
+    let mut platform = get_input(false).parse::<Platform>().unwrap();
+    platform.tilt(Direction::North);
+    println!("North load: {}", platform.north_load());
+}
+
+pub fn day_14_part_2() {
+    let platform = get_input(false).parse::<Platform>().unwrap();
+    println!("North load after spins: {}", platform.load_after_spins(SPIN_CYCLES));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        O....#....
+        O.OO#....#
+        .....##...
+        OO.#O....O
+        .O.....O#.
+        O.#..O.#.#
+        ..O..#O..O
+        .......O..
+        #....###..
+        #OO..#....
+    */
+
+    #[test]
+    fn test_parse() {
+        let platform = get_input(true).parse::<Platform>().unwrap();
+
+        assert_eq!(platform.width(), 10);
+        assert_eq!(platform.height(), 10);
+        assert_eq!(platform.rocks[0][0], Rock::Round);
+        assert_eq!(platform.rocks[0][5], Rock::Cube);
+        assert_eq!(platform.rocks[0][1], Rock::Empty);
+    }
+
+    #[test]
+    fn test_tilt_north() {
+        let mut platform = get_input(true).parse::<Platform>().unwrap();
+        platform.tilt(Direction::North);
+
+        let expected = "OOOO.#.O..\nOO..#....#\nOO..O##..O\nO..#.OO...\n........#.\n..#....#.#\n..O..#.O.O\n..O.......\n#....###..\n#....#....";
+        assert_eq!(platform, expected.parse::<Platform>().unwrap());
+        assert_eq!(platform.north_load(), 136);
+    }
+
+    #[test]
+    fn test_spin() {
+        let mut platform = get_input(true).parse::<Platform>().unwrap();
+        platform.spin();
+
+        let expected = ".....#....\n....#...O#\n...OO##...\n.OO#......\n.....OOO#.\n.O#...O#.#\n....O#....\n......OOOO\n#...O###..\n#..OO#....";
+        assert_eq!(platform, expected.parse::<Platform>().unwrap());
+    }
+
+    #[test]
+    fn test_tilt_east() {
+        let mut platform = "O.#O.\n.OO..".parse::<Platform>().unwrap();
+        platform.tilt(Direction::East);
+
+        assert_eq!(platform, ".O#.O\n...OO".parse::<Platform>().unwrap());
+    }
+
+    #[test]
+    fn test_load_after_spins() {
+        let platform = get_input(true).parse::<Platform>().unwrap();
+
+        assert_eq!(platform.load_after_spins(SPIN_CYCLES), 64);
+    }
+}
@@ -0,0 +1,198 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_15_test.txt"),
+        false => include_str!("../../src/inputs/day_15.txt")
+    }
+}
+
+const NB_BOXES: usize = 256;
+
+pub fn hash(input: &str) -> usize {
+    input.bytes().fold(0, |acc, x| (acc + x as usize) * 17 % NB_BOXES)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Operation {
+    Insert(String, u32),
+    Remove(String),
+}
+
+impl std::str::FromStr for Operation {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some(label) = s.strip_suffix('-') {
+            return Ok(Operation::Remove(label.to_string()));
+        }
+
+        let (label, focal_length) = s.split_once('=')
+            .ok_or(format!("Unknown operation: {}", s))?;
+
+        Ok(Operation::Insert(label.to_string(), focal_length.parse::<u32>()?))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Lens {
+    label: String,
+    focal_length: u32,
+}
+
+/// The HASHMAP: 256 boxes, each keeping its lenses in insertion order.
+#[derive(Debug)]
+pub struct LensBoxes {
+    boxes: Vec<Vec<Lens>>,
+}
+
+impl Default for LensBoxes {
+    fn default() -> Self {
+        LensBoxes::new()
+    }
+}
+
+impl LensBoxes {
+    pub fn new() -> LensBoxes {
+        LensBoxes {
+            boxes: vec![Vec::new(); NB_BOXES],
+        }
+    }
+
+    /// Replaces the lens with the same label in place, or adds the lens at the back of its box.
+    fn insert(&mut self, label: &str, focal_length: u32) {
+        let lenses = &mut self.boxes[hash(label)];
+
+        match lenses.iter_mut().find(|x| x.label == label) {
+            Some(lens) => lens.focal_length = focal_length,
+            None => lenses.push(Lens {
+                label: label.to_string(),
+                focal_length,
+            }),
+        }
+    }
+
+    /// Takes the lens out of its box, keeping the order of the others.
+    fn remove(&mut self, label: &str) {
+        self.boxes[hash(label)].retain(|x| x.label != label);
+    }
+
+    fn get(&self, label: &str) -> Option<&Lens> {
+        self.boxes[hash(label)].iter().find(|x| x.label == label)
+    }
+
+    pub fn apply(&mut self, operation: &Operation) {
+        match operation {
+            Operation::Insert(label, focal_length) => self.insert(label, *focal_length),
+            Operation::Remove(label) => self.remove(label),
+        }
+    }
+
+    pub fn focusing_power(&self) -> u32 {
+        let mut power = 0;
+        for (box_index, lenses) in self.boxes.iter().enumerate() {
+            for (slot, lens) in lenses.iter().enumerate() {
+                power += (box_index as u32 + 1) * (slot as u32 + 1) * lens.focal_length;
+            }
+        }
+
+        power
+    }
+}
+
+pub fn parse_sequence(input: &str) -> Vec<&str> {
+    input
+        .split(',')
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<&str>>()
+}
+
+pub fn day_15_part_1() {
+    let sum: usize = parse_sequence(get_input(false)).iter().map(|x| hash(x)).sum();
+    println!("Sum of hashes: {}", sum);
+}
+
+pub fn day_15_part_2() {
+    let mut boxes = LensBoxes::new();
+    for step in parse_sequence(get_input(false)) {
+        boxes.apply(&step.parse::<Operation>().unwrap());
+    }
+
+    println!("Focusing power: {}", boxes.focusing_power());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
+    */
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash("HASH"), 52);
+        assert_eq!(hash("rn"), 0);
+        assert_eq!(hash("qp"), 1);
+        assert_eq!(hash("pc"), 3);
+
+        let sum: usize = parse_sequence(get_input(true)).iter().map(|x| hash(x)).sum();
+        assert_eq!(sum, 1320);
+    }
+
+    #[test]
+    fn test_parse_operation() {
+        assert_eq!("rn=1".parse::<Operation>().unwrap(), Operation::Insert("rn".to_string(), 1));
+        assert_eq!("cm-".parse::<Operation>().unwrap(), Operation::Remove("cm".to_string()));
+        assert!("cm".parse::<Operation>().is_err());
+        assert!("cm=x".parse::<Operation>().is_err());
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut boxes = LensBoxes::new();
+        boxes.insert("rn", 1);
+        boxes.insert("cm", 2);
+
+        assert_eq!(boxes.boxes[0].iter().map(|x| x.label.as_str()).collect::<Vec<&str>>(), vec!["rn", "cm"]);
+        assert_eq!(boxes.get("cm").unwrap().focal_length, 2);
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut boxes = LensBoxes::new();
+        boxes.insert("rn", 1);
+        boxes.insert("cm", 2);
+        boxes.insert("rn", 7);
+
+        assert_eq!(boxes.boxes[0].iter().map(|x| x.label.as_str()).collect::<Vec<&str>>(), vec!["rn", "cm"]);
+        assert_eq!(boxes.get("rn").unwrap().focal_length, 7);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut boxes = LensBoxes::new();
+        boxes.insert("rn", 1);
+        boxes.insert("cm", 2);
+        boxes.remove("rn");
+        boxes.remove("qp");
+
+        assert_eq!(boxes.get("rn"), None);
+        assert_eq!(boxes.boxes[0].iter().map(|x| x.label.as_str()).collect::<Vec<&str>>(), vec!["cm"]);
+    }
+
+    #[test]
+    fn test_focusing_power() {
+        let mut boxes = LensBoxes::new();
+        for step in parse_sequence(get_input(true)) {
+            boxes.apply(&step.parse::<Operation>().unwrap());
+        }
+
+        assert_eq!(boxes.focusing_power(), 145);
+    }
+}
@@ -0,0 +1,252 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::thread;
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_16_test.txt"),
+        false => include_str!("../../src/inputs/day_16.txt")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tile {
+    Empty,
+    MirrorSlash,
+    MirrorBackslash,
+    SplitterVertical,
+    SplitterHorizontal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// A distinct bit per direction, to store every direction a tile was crossed in in a single byte.
+    fn bit(&self) -> u8 {
+        match self {
+            Direction::Up => 1,
+            Direction::Down => 2,
+            Direction::Left => 4,
+            Direction::Right => 8,
+        }
+    }
+}
+
+impl Tile {
+    /// The directions a beam leaves the tile in, when it enters it going in `direction`.
+    fn outgoing(&self, direction: Direction) -> Vec<Direction> {
+        match (self, direction) {
+            (Tile::MirrorSlash, Direction::Up) => vec![Direction::Right],
+            (Tile::MirrorSlash, Direction::Down) => vec![Direction::Left],
+            (Tile::MirrorSlash, Direction::Left) => vec![Direction::Down],
+            (Tile::MirrorSlash, Direction::Right) => vec![Direction::Up],
+            (Tile::MirrorBackslash, Direction::Up) => vec![Direction::Left],
+            (Tile::MirrorBackslash, Direction::Down) => vec![Direction::Right],
+            (Tile::MirrorBackslash, Direction::Left) => vec![Direction::Up],
+            (Tile::MirrorBackslash, Direction::Right) => vec![Direction::Down],
+            (Tile::SplitterVertical, Direction::Left | Direction::Right) => vec![Direction::Up, Direction::Down],
+            (Tile::SplitterHorizontal, Direction::Up | Direction::Down) => vec![Direction::Left, Direction::Right],
+            _ => vec![direction],
+        }
+    }
+}
+
+/// Where a beam enters the contraption, and the direction it is heading.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Entry {
+    pub x: usize,
+    pub y: usize,
+    pub direction: Direction,
+}
+
+#[derive(Debug)]
+pub struct Contraption {
+    tiles: Vec<Vec<Tile>>,
+}
+
+impl std::str::FromStr for Contraption {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = s.lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .map(|line| line.chars().map(|c| match c {
+                '.' => Ok(Tile::Empty),
+                '/' => Ok(Tile::MirrorSlash),
+                '\\' => Ok(Tile::MirrorBackslash),
+                '|' => Ok(Tile::SplitterVertical),
+                '-' => Ok(Tile::SplitterHorizontal),
+                _ => Err(format!("Unknown tile: {}", c)),
+            }).collect::<Result<Vec<Tile>, String>>())
+            .collect::<Result<Vec<Vec<Tile>>, String>>()?;
+
+        if tiles.iter().any(|x| x.len() != tiles[0].len()) {
+            return Err("All the lines of the contraption must have the same width".into());
+        }
+
+        Ok(Contraption {
+            tiles,
+        })
+    }
+}
+
+impl Contraption {
+    fn height(&self) -> usize {
+        self.tiles.len()
+    }
+
+    fn width(&self) -> usize {
+        self.tiles.first().map_or(0, |x| x.len())
+    }
+
+    fn step(&self, x: usize, y: usize, direction: Direction) -> Option<(usize, usize)> {
+        match direction {
+            Direction::Up if y > 0 => Some((x, y - 1)),
+            Direction::Down if y + 1 < self.height() => Some((x, y + 1)),
+            Direction::Left if x > 0 => Some((x - 1, y)),
+            Direction::Right if x + 1 < self.width() => Some((x + 1, y)),
+            _ => None,
+        }
+    }
+
+    /// Follows every beam from the entry, and counts the tiles crossed by at least one of them.
+    pub fn energized(&self, entry: Entry) -> usize {
+        if self.width() == 0 || self.height() == 0 {
+            return 0;
+        }
+
+        let mut visited: Vec<Vec<u8>> = vec![vec![0; self.width()]; self.height()];
+        let mut beams: Vec<Entry> = vec![entry];
+
+        while let Some(beam) = beams.pop() {
+            if visited[beam.y][beam.x] & beam.direction.bit() != 0 {
+                continue;
+            }
+            visited[beam.y][beam.x] |= beam.direction.bit();
+
+            for direction in self.tiles[beam.y][beam.x].outgoing(beam.direction) {
+                if let Some((x, y)) = self.step(beam.x, beam.y, direction) {
+                    beams.push(Entry { x, y, direction });
+                }
+            }
+        }
+
+        visited.iter().flatten().filter(|x| **x != 0).count()
+    }
+
+    /// Every tile on the edges, with the beam heading inwards.
+    fn edge_entries(&self) -> Vec<Entry> {
+        let (width, height) = (self.width(), self.height());
+        let mut entries: Vec<Entry> = Vec::new();
+
+        for x in 0..width {
+            entries.push(Entry { x, y: 0, direction: Direction::Down });
+            entries.push(Entry { x, y: height - 1, direction: Direction::Up });
+        }
+        for y in 0..height {
+            entries.push(Entry { x: 0, y, direction: Direction::Right });
+            entries.push(Entry { x: width - 1, y, direction: Direction::Left });
+        }
+
+        entries
+    }
+
+    /// Tries every edge entry, split across `threads` threads, and keeps the one energizing the most tiles.
+    pub fn best_entry(&self, threads: usize) -> Option<(Entry, usize)> {
+        let entries = self.edge_entries();
+        if entries.is_empty() {
+            return None;
+        }
+
+        let chunk_size = entries.len().div_ceil(threads.max(1));
+
+        thread::scope(|scope| {
+            let handles = entries
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || {
+                    chunk.iter().map(|x| (*x, self.energized(*x))).max_by_key(|x| x.1)
+                }))
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .filter_map(|x| x.join().unwrap())
+                .max_by_key(|x| x.1)
+        })
+    }
+}
+
+pub fn day_16_part_1() {
+    let contraption = get_input(false).parse::<Contraption>().unwrap();
+    let energized = contraption.energized(Entry { x: 0, y: 0, direction: Direction::Right });
+    println!("Energized tiles: {}", energized);
+}
+
+pub fn day_16_part_2() {
+    let contraption = get_input(false).parse::<Contraption>().unwrap();
+    let threads = thread::available_parallelism().map_or(1, |x| x.get());
+    let (entry, energized) = contraption.best_entry(threads).unwrap();
+    println!("Best entry: {:?}", entry);
+    println!("Energized tiles: {}", energized);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        .|...\....
+        |.-.\.....
+        .....|-...
+        ........|.
+        ..........
+        .........\
+        ..../.\\..
+        .-.-/..|..
+        .|....-|.\
+        ..//.|....
+    */
+
+    #[test]
+    fn test_parse() {
+        let contraption = get_input(true).parse::<Contraption>().unwrap();
+
+        assert_eq!(contraption.width(), 10);
+        assert_eq!(contraption.height(), 10);
+        assert_eq!(contraption.tiles[0][1], Tile::SplitterVertical);
+        assert_eq!(contraption.tiles[0][5], Tile::MirrorBackslash);
+        assert_eq!(contraption.tiles[1][2], Tile::SplitterHorizontal);
+        assert_eq!(contraption.tiles[6][4], Tile::MirrorSlash);
+    }
+
+    #[test]
+    fn test_energized() {
+        let contraption = get_input(true).parse::<Contraption>().unwrap();
+
+        assert_eq!(contraption.energized(Entry { x: 0, y: 0, direction: Direction::Right }), 46);
+    }
+
+    #[test]
+    fn test_energized_empty() {
+        let contraption = "".parse::<Contraption>().unwrap();
+
+        assert_eq!(contraption.energized(Entry { x: 0, y: 0, direction: Direction::Right }), 0);
+        assert_eq!(contraption.best_entry(1), None);
+    }
+
+    #[test]
+    fn test_best_entry() {
+        let contraption = get_input(true).parse::<Contraption>().unwrap();
+
+        assert_eq!(contraption.best_entry(1), Some((Entry { x: 3, y: 0, direction: Direction::Down }, 51)));
+        assert_eq!(contraption.best_entry(4).unwrap().1, 51);
+    }
+}
@@ -0,0 +1,265 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+
+use crate::utils::search::a_star;
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_17_test.txt"),
+        false => include_str!("../../src/inputs/day_17.txt")
+    }
+}
+
+fn get_input_2(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_17_test_2.txt"),
+        false => include_str!("../../src/inputs/day_17.txt")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn turns(&self) -> [Direction; 2] {
+        match self {
+            Direction::Up | Direction::Down => [Direction::Left, Direction::Right],
+            Direction::Left | Direction::Right => [Direction::Up, Direction::Down],
+        }
+    }
+}
+
+/// How many blocks in a row a crucible must, and can, move straight.
+#[derive(Debug, Clone, Copy)]
+pub struct Crucible {
+    min_run: usize,
+    max_run: usize,
+}
+
+pub const NORMAL_CRUCIBLE: Crucible = Crucible { min_run: 1, max_run: 3 };
+pub const ULTRA_CRUCIBLE: Crucible = Crucible { min_run: 4, max_run: 10 };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    x: usize,
+    y: usize,
+    direction: Direction,
+    run: usize,
+}
+
+#[derive(Debug)]
+pub struct City {
+    heat_loss: Vec<Vec<u64>>,
+}
+
+impl std::str::FromStr for City {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let heat_loss = s.lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .map(|line| line.chars()
+                .map(|c| c.to_digit(10).map(u64::from).ok_or(format!("Unknown block: {}", c)))
+                .collect::<Result<Vec<u64>, String>>())
+            .collect::<Result<Vec<Vec<u64>>, String>>()?;
+
+        if heat_loss.iter().any(|x| x.len() != heat_loss[0].len()) {
+            return Err("All the lines of the city must have the same width".into());
+        }
+
+        Ok(City {
+            heat_loss,
+        })
+    }
+}
+
+impl City {
+    fn height(&self) -> usize {
+        self.heat_loss.len()
+    }
+
+    fn width(&self) -> usize {
+        self.heat_loss.first().map_or(0, |x| x.len())
+    }
+
+    fn step(&self, x: usize, y: usize, direction: Direction) -> Option<(usize, usize)> {
+        match direction {
+            Direction::Up if y > 0 => Some((x, y - 1)),
+            Direction::Down if y + 1 < self.height() => Some((x, y + 1)),
+            Direction::Left if x > 0 => Some((x - 1, y)),
+            Direction::Right if x + 1 < self.width() => Some((x + 1, y)),
+            _ => None,
+        }
+    }
+
+    fn neighbours(&self, state: &State, crucible: Crucible) -> Vec<(State, u64)> {
+        let mut directions: Vec<Direction> = Vec::new();
+        if state.run < crucible.max_run {
+            directions.push(state.direction);
+        }
+        if state.run >= crucible.min_run {
+            directions.extend(state.direction.turns());
+        }
+
+        directions
+            .into_iter()
+            .filter_map(|direction| {
+                let (x, y) = self.step(state.x, state.y, direction)?;
+                let run = if direction == state.direction { state.run + 1 } else { 1 };
+                Some((State { x, y, direction, run }, self.heat_loss[y][x]))
+            })
+            .collect()
+    }
+
+    /// The crucible starts on the top-left block, about to move right or down.
+    fn starts(&self) -> Vec<State> {
+        vec![
+            State { x: 0, y: 0, direction: Direction::Right, run: 0 },
+            State { x: 0, y: 0, direction: Direction::Down, run: 0 },
+        ]
+    }
+
+    /// The crucible can stop on the bottom-right block once it moved straight long enough, or right away when it
+    /// starts there.
+    fn is_goal(&self, state: &State, crucible: Crucible) -> bool {
+        state.x + 1 == self.width() && state.y + 1 == self.height() && (state.run == 0 || state.run >= crucible.min_run)
+    }
+
+    /// The least heat lost going from the top-left block to the bottom-right one.
+    pub fn least_heat_loss(&self, crucible: Crucible) -> Option<u64> {
+        if self.width() == 0 || self.height() == 0 {
+            return None;
+        }
+
+        let (target_x, target_y) = (self.width() - 1, self.height() - 1);
+        let least_loss = self.heat_loss.iter().flatten().min().copied().unwrap_or(0);
+
+        a_star(
+            self.starts(),
+            |state| self.neighbours(state, crucible),
+            // Every block left to enter loses at least the least heat of any block
+            |state| ((target_x - state.x) + (target_y - state.y)) as u64 * least_loss,
+            |state| self.is_goal(state, crucible),
+        ).map(|(_, heat_loss)| heat_loss)
+    }
+}
+
+pub fn day_17_part_1() {
+    let city = get_input(false).parse::<City>().unwrap();
+    println!("Least heat loss: {}", city.least_heat_loss(NORMAL_CRUCIBLE).unwrap());
+}
+
+pub fn day_17_part_2() {
+    let city = get_input(false).parse::<City>().unwrap();
+    println!("Least heat loss: {}", city.least_heat_loss(ULTRA_CRUCIBLE).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::differential::{compare, shrink_lines};
+    use crate::utils::random::Random;
+    use crate::utils::search::dijkstra;
+
+    /*
+        2413432311323
+        3215453535623
+        3255245654254
+        3446585845452
+        4546657867536
+        1438598798454
+        4457876987766
+        3637877979653
+        4654967986887
+        4564679986453
+        1224686865563
+        2546548887735
+        4322674655533
+    */
+
+    #[test]
+    fn test_parse() {
+        let city = get_input(true).parse::<City>().unwrap();
+
+        assert_eq!(city.width(), 13);
+        assert_eq!(city.height(), 13);
+        assert_eq!(city.heat_loss[0][0], 2);
+        assert_eq!(city.heat_loss[12][12], 3);
+    }
+
+    #[test]
+    fn test_normal_crucible() {
+        let city = get_input(true).parse::<City>().unwrap();
+
+        assert_eq!(city.least_heat_loss(NORMAL_CRUCIBLE), Some(102));
+    }
+
+    #[test]
+    fn test_ultra_crucible() {
+        let city = get_input(true).parse::<City>().unwrap();
+        let other_city = get_input_2(true).parse::<City>().unwrap();
+
+        assert_eq!(city.least_heat_loss(ULTRA_CRUCIBLE), Some(94));
+        assert_eq!(other_city.least_heat_loss(ULTRA_CRUCIBLE), Some(71));
+    }
+
+    #[test]
+    fn test_empty_city() {
+        let city = "".parse::<City>().unwrap();
+
+        assert_eq!(city.least_heat_loss(NORMAL_CRUCIBLE), None);
+        assert_eq!(city.least_heat_loss(ULTRA_CRUCIBLE), None);
+    }
+
+    #[test]
+    fn test_single_block() {
+        let city = "7".parse::<City>().unwrap();
+
+        assert_eq!(city.least_heat_loss(NORMAL_CRUCIBLE), Some(0));
+        assert_eq!(city.least_heat_loss(ULTRA_CRUCIBLE), Some(0));
+    }
+
+    #[test]
+    fn test_blocks_without_heat_loss() {
+        let city = "0400\n0070\n0260\n9040".parse::<City>().unwrap();
+
+        assert_eq!(city.least_heat_loss(NORMAL_CRUCIBLE), Some(4));
+    }
+
+    #[test]
+    fn test_a_star_matches_dijkstra() {
+        let generate = |seed: u64| {
+            let mut random = Random::new(seed);
+            let (width, height) = (random.range(1..7), random.range(1..7));
+            (0..height)
+                .map(|_| (0..width).map(|_| char::from(b'0' + random.range(0..10) as u8)).collect::<String>())
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+
+        for crucible in [NORMAL_CRUCIBLE, ULTRA_CRUCIBLE] {
+            let result = compare(
+                0..3000,
+                generate,
+                |x| shrink_lines(x, |_| x.lines().count() > 1),
+                |x| {
+                    let city = x.parse::<City>().unwrap();
+                    dijkstra(city.starts(), |state| city.neighbours(state, crucible), |state| city.is_goal(state, crucible))
+                        .map(|(_, heat_loss)| heat_loss)
+                },
+                |x| x.parse::<City>().unwrap().least_heat_loss(crucible),
+            );
+
+            if let Err(mismatch) = result {
+                panic!("{}", mismatch);
+            }
+        }
+    }
+}
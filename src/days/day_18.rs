@@ -0,0 +1,233 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+
+use crate::utils::polygon::enclosed_points;
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_18_test.txt"),
+        false => include_str!("../../src/inputs/day_18.txt")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn delta(&self) -> (i64, i64) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Instruction {
+    direction: Direction,
+    distance: i64,
+}
+
+#[derive(Debug)]
+pub struct Step {
+    pub instruction: Instruction,
+    color: String,
+}
+
+impl std::str::FromStr for Step {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split_whitespace().collect::<Vec<&str>>();
+        if parts.len() != 3 {
+            return Err(format!("Step must have a direction, a distance and a color: {}", s).into());
+        }
+
+        let direction = match parts[0] {
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            "L" => Direction::Left,
+            "R" => Direction::Right,
+            _ => return Err(format!("Unknown direction: {}", parts[0]).into()),
+        };
+
+        let distance = parts[1].parse::<i64>()?;
+        if distance <= 0 {
+            return Err(format!("Distance must be positive: {}", distance).into());
+        }
+
+        let color = parts[2]
+            .strip_prefix("(#")
+            .and_then(|x| x.strip_suffix(')'))
+            .ok_or(format!("Color must look like (#rrggbb): {}", parts[2]))?;
+
+        if color.len() != 6 || !color.chars().all(|x| x.is_ascii_hexdigit()) {
+            return Err(format!("Color must have 6 hexadecimal digits: {}", color).into());
+        }
+        if !color.ends_with(['0', '1', '2', '3']) {
+            return Err(format!("Color must end with a direction from 0 to 3: {}", color).into());
+        }
+        if color.starts_with("00000") {
+            return Err(format!("Color must hold a positive distance: {}", color).into());
+        }
+
+        Ok(Step {
+            instruction: Instruction { direction, distance },
+            color: color.to_string(),
+        })
+    }
+}
+
+impl Step {
+    /// The real instruction hidden in the color: 5 hex digits of distance, then the direction from 0 to 3.
+    pub fn decode_color(&self) -> Instruction {
+        let distance = i64::from_str_radix(&self.color[..5], 16).unwrap();
+        let direction = match &self.color[5..] {
+            "0" => Direction::Right,
+            "1" => Direction::Down,
+            "2" => Direction::Left,
+            "3" => Direction::Up,
+            x => unreachable!("Error: Step::from_str only accepts directions from 0 to 3, got {}", x),
+        };
+
+        Instruction { direction, distance }
+    }
+}
+
+pub fn parse_steps(input: &str) -> Result<Vec<Step>, Box<dyn Error>> {
+    input
+        .lines()
+        .filter(|x| !x.trim().is_empty())
+        .map(|x| x.parse::<Step>())
+        .collect::<Result<Vec<Step>, _>>()
+}
+
+/// The corners of the trench, in digging order from the origin. The trench is closed only if the instructions lead
+/// back to the origin, which is not checked.
+fn dig(instructions: &[Instruction]) -> Vec<(i64, i64)> {
+    let mut vertices: Vec<(i64, i64)> = Vec::new();
+    let (mut x, mut y) = (0, 0);
+
+    for instruction in instructions {
+        vertices.push((x, y));
+        let (dx, dy) = instruction.direction.delta();
+        x += dx * instruction.distance;
+        y += dy * instruction.distance;
+    }
+
+    vertices
+}
+
+/// The trench and the points it encloses. Nothing is dug without instructions.
+pub fn lagoon_size(instructions: &[Instruction]) -> i64 {
+    if instructions.is_empty() {
+        return 0;
+    }
+
+    enclosed_points(&dig(instructions))
+}
+
+pub fn day_18_part_1() {
+    let instructions = parse_steps(get_input(false)).unwrap()
+        .iter()
+        .map(|x| x.instruction)
+        .collect::<Vec<Instruction>>();
+    println!("Lagoon size: {}", lagoon_size(&instructions));
+}
+
+pub fn day_18_part_2() {
+    let instructions = parse_steps(get_input(false)).unwrap()
+        .iter()
+        .map(|x| x.decode_color())
+        .collect::<Vec<Instruction>>();
+    println!("Lagoon size: {}", lagoon_size(&instructions));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        R 6 (#70c710)
+        D 5 (#0dc571)
+        L 2 (#5713f0)
+        D 2 (#d2c081)
+        R 2 (#59c680)
+        D 2 (#411b91)
+        L 5 (#8ceee2)
+        U 2 (#caa173)
+        L 1 (#1b58a2)
+        U 2 (#caa171)
+        R 2 (#7807d2)
+        U 3 (#a77fa3)
+        L 2 (#015232)
+        U 2 (#7a21e3)
+    */
+
+    #[test]
+    fn test_parse() {
+        let steps = parse_steps(get_input(true)).unwrap();
+
+        assert_eq!(steps.len(), 14);
+        assert_eq!(steps[0].instruction, Instruction { direction: Direction::Right, distance: 6 });
+        assert_eq!(steps[0].color, "70c710");
+    }
+
+    #[test]
+    fn test_decode_color() {
+        let steps = parse_steps(get_input(true)).unwrap();
+
+        assert_eq!(steps[0].decode_color(), Instruction { direction: Direction::Right, distance: 461937 });
+        assert_eq!(steps[1].decode_color(), Instruction { direction: Direction::Down, distance: 56407 });
+        assert_eq!(steps[7].decode_color(), Instruction { direction: Direction::Up, distance: 829975 });
+    }
+
+    #[test]
+    fn test_lagoon_size() {
+        let instructions = parse_steps(get_input(true)).unwrap()
+            .iter()
+            .map(|x| x.instruction)
+            .collect::<Vec<Instruction>>();
+
+        assert_eq!(lagoon_size(&instructions), 62);
+    }
+
+    #[test]
+    fn test_lagoon_size_from_colors() {
+        let instructions = parse_steps(get_input(true)).unwrap()
+            .iter()
+            .map(|x| x.decode_color())
+            .collect::<Vec<Instruction>>();
+
+        assert_eq!(lagoon_size(&instructions), 952408144115);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!("X 6 (#70c710)".parse::<Step>().is_err());
+        assert!("R 6 (#70c71)".parse::<Step>().is_err());
+        assert!("R 6".parse::<Step>().is_err());
+        assert_eq!(
+            "R 6 (#70c714)".parse::<Step>().unwrap_err().to_string(),
+            "Color must end with a direction from 0 to 3: 70c714"
+        );
+        assert!("R 6 (#70c71f)".parse::<Step>().is_err());
+        assert!("R 0 (#70c710)".parse::<Step>().is_err());
+        assert!("R -6 (#70c710)".parse::<Step>().is_err());
+        assert!("R 6 (#000000)".parse::<Step>().is_err());
+        assert!(parse_steps("R 6 (#70c710)\nR x (#70c710)").is_err());
+    }
+
+    #[test]
+    fn test_no_instructions() {
+        assert_eq!(lagoon_size(&parse_steps("").unwrap().iter().map(|x| x.instruction).collect::<Vec<Instruction>>()), 0);
+    }
+}
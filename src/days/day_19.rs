@@ -0,0 +1,466 @@
+#![allow(dead_code)]
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::ops::Range;
+
+use crate::utils::parse::split_blocks;
+use crate::utils::range::split_at;
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_19_test.txt"),
+        false => include_str!("../../src/inputs/day_19.txt")
+    }
+}
+
+const START_WORKFLOW: &str = "in";
+const RATING_RANGE: Range<u64> = 1..4001;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl Category {
+    fn index(&self) -> usize {
+        match self {
+            Category::X => 0,
+            Category::M => 1,
+            Category::A => 2,
+            Category::S => 3,
+        }
+    }
+}
+
+impl std::str::FromStr for Category {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "x" => Ok(Category::X),
+            "m" => Ok(Category::M),
+            "a" => Ok(Category::A),
+            "s" => Ok(Category::S),
+            _ => Err(format!("Unknown category: {}", s).into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+impl std::str::FromStr for Target {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" => Ok(Target::Accept),
+            "R" => Ok(Target::Reject),
+            "" => Err("Target is empty".into()),
+            name => Ok(Target::Workflow(name.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Condition {
+    LessThan(Category, u64),
+    GreaterThan(Category, u64),
+}
+
+impl Condition {
+    fn matches(&self, part: &Part) -> bool {
+        match self {
+            Condition::LessThan(category, value) => part.ratings[category.index()] < *value,
+            Condition::GreaterThan(category, value) => part.ratings[category.index()] > *value,
+        }
+    }
+
+    /// Splits the ratings into the ones matching the condition and the ones that do not.
+    fn split(&self, ratings: &Ratings) -> (Option<Ratings>, Option<Ratings>) {
+        let (category, below, above) = match self {
+            Condition::LessThan(category, value) => {
+                let (below, above) = split_at(&ratings[category.index()], *value);
+                (category, below, above)
+            }
+            Condition::GreaterThan(category, value) => {
+                let range = &ratings[category.index()];
+                // No rating is greater than u64::MAX
+                let (below, above) = match value.checked_add(1) {
+                    Some(at) => split_at(range, at),
+                    None => split_at(range, range.end),
+                };
+                (category, above, below)
+            }
+        };
+
+        let with = |range: Range<u64>| {
+            let mut ratings = ratings.clone();
+            ratings[category.index()] = range;
+            ratings
+        };
+
+        (below.map(with), above.map(with))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    condition: Option<Condition>,
+    target: Target,
+}
+
+impl std::str::FromStr for Rule {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((condition, target)) = s.split_once(':') else {
+            return Ok(Rule { condition: None, target: s.parse::<Target>()? });
+        };
+
+        let condition = if let Some((category, value)) = condition.split_once('<') {
+            Condition::LessThan(category.parse::<Category>()?, value.parse::<u64>()?)
+        } else if let Some((category, value)) = condition.split_once('>') {
+            Condition::GreaterThan(category.parse::<Category>()?, value.parse::<u64>()?)
+        } else {
+            return Err(format!("Unknown condition: {}", condition).into());
+        };
+
+        Ok(Rule {
+            condition: Some(condition),
+            target: target.parse::<Target>()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Workflow {
+    name: String,
+    rules: Vec<Rule>,
+}
+
+impl std::str::FromStr for Workflow {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, rules) = s.trim()
+            .strip_suffix('}')
+            .and_then(|x| x.split_once('{'))
+            .ok_or(format!("Workflow must look like name{{rules}}: {}", s))?;
+
+        let rules = rules.split(',')
+            .map(|x| x.parse::<Rule>())
+            .collect::<Result<Vec<Rule>, _>>()?;
+
+        if rules.last().is_some_and(|x| x.condition.is_some()) {
+            return Err(format!("Last rule of workflow {} must have no condition", name).into());
+        }
+
+        Ok(Workflow {
+            name: name.to_string(),
+            rules,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Part {
+    ratings: [u64; 4],
+}
+
+impl std::str::FromStr for Part {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ratings = s.trim()
+            .strip_prefix('{')
+            .and_then(|x| x.strip_suffix('}'))
+            .ok_or(format!("Part must look like {{x=..,m=..,a=..,s=..}}: {}", s))?;
+
+        let mut part = Part { ratings: [0; 4] };
+        for rating in ratings.split(',') {
+            let (category, value) = rating.split_once('=').ok_or(format!("Unknown rating: {}", rating))?;
+            part.ratings[category.parse::<Category>()?.index()] = value.parse::<u64>()?;
+        }
+
+        Ok(part)
+    }
+}
+
+impl Part {
+    fn total_rating(&self) -> u64 {
+        self.ratings.iter().sum()
+    }
+}
+
+/// A hypercube of ratings: one half-open range per category.
+type Ratings = [Range<u64>; 4];
+
+#[derive(Debug)]
+pub struct System {
+    workflows: HashMap<String, Workflow>,
+    parts: Vec<Part>,
+}
+
+impl std::str::FromStr for System {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let blocks = split_blocks(s);
+        if blocks.len() != 2 {
+            return Err("System must have a block of workflows and a block of parts".into());
+        }
+
+        let mut workflows: HashMap<String, Workflow> = HashMap::new();
+        for line in blocks[0].lines() {
+            let workflow = line.parse::<Workflow>()?;
+            if workflows.contains_key(&workflow.name) {
+                return Err(format!("Duplicate workflow: {}", workflow.name).into());
+            }
+            workflows.insert(workflow.name.clone(), workflow);
+        }
+
+        let parts = blocks[1].lines()
+            .map(|x| x.parse::<Part>())
+            .collect::<Result<Vec<Part>, _>>()?;
+
+        let system = System {
+            workflows,
+            parts,
+        };
+        system.validate()?;
+
+        Ok(system)
+    }
+}
+
+impl System {
+    /// Checks that the start workflow and every target exist, and that no workflow reachable from the start leads back
+    /// to itself, so every part ends up accepted or rejected.
+    fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if !self.workflows.contains_key(START_WORKFLOW) {
+            return Err(format!("Missing workflow: {}", START_WORKFLOW).into());
+        }
+
+        for workflow in self.workflows.values() {
+            for rule in workflow.rules.iter() {
+                if let Target::Workflow(name) = &rule.target {
+                    if !self.workflows.contains_key(name) {
+                        return Err(format!("Workflow {} sends parts to unknown workflow {}", workflow.name, name).into());
+                    }
+                }
+            }
+        }
+
+        // Depth-first search, with the workflows on the current path and the index of the next rule to follow in each
+        let mut path: Vec<(&str, usize)> = vec![(START_WORKFLOW, 0)];
+        let mut on_path: HashSet<&str> = HashSet::from([START_WORKFLOW]);
+        let mut done: HashSet<&str> = HashSet::new();
+
+        while let Some((name, index)) = path.pop() {
+            let Some(rule) = self.workflow(name).rules.get(index) else {
+                on_path.remove(name);
+                done.insert(name);
+                continue;
+            };
+            path.push((name, index + 1));
+
+            if let Target::Workflow(next) = &rule.target {
+                if on_path.contains(next.as_str()) {
+                    return Err(format!("Workflows loop through {}", next).into());
+                }
+                if !done.contains(next.as_str()) {
+                    on_path.insert(next);
+                    path.push((next, 0));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn workflow(&self, name: &str) -> &Workflow {
+        self.workflows
+            .get(name)
+            .unwrap_or_else(|| panic!("Error: unknown workflow: {}", name))
+    }
+
+    fn is_accepted(&self, part: &Part) -> bool {
+        let mut workflow = self.workflow(START_WORKFLOW);
+
+        loop {
+            let rule = workflow.rules
+                .iter()
+                .find(|x| x.condition.is_none_or(|condition| condition.matches(part)))
+                .unwrap();
+
+            match &rule.target {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::Workflow(name) => workflow = self.workflow(name),
+            }
+        }
+    }
+
+    pub fn sum_accepted_ratings(&self) -> u64 {
+        self.parts
+            .iter()
+            .filter(|x| self.is_accepted(x))
+            .map(|x| x.total_rating())
+            .sum()
+    }
+
+    /// Counts the combinations of ratings accepted by the workflows, by splitting the ratings through each rule.
+    fn count_accepted(&self, ratings: Ratings, target: &Target) -> u64 {
+        match target {
+            Target::Accept => ratings.iter().map(|x| x.end - x.start).product(),
+            Target::Reject => 0,
+            Target::Workflow(name) => {
+                let mut count = 0;
+                let mut remaining = Some(ratings);
+
+                for rule in self.workflow(name).rules.iter() {
+                    let Some(ratings) = remaining.take() else {
+                        break;
+                    };
+
+                    match rule.condition {
+                        Some(condition) => {
+                            let (matching, rest) = condition.split(&ratings);
+                            if let Some(matching) = matching {
+                                count += self.count_accepted(matching, &rule.target);
+                            }
+                            remaining = rest;
+                        }
+                        None => count += self.count_accepted(ratings, &rule.target),
+                    }
+                }
+
+                count
+            }
+        }
+    }
+
+    pub fn count_all_accepted(&self) -> u64 {
+        let ratings: Ratings = [RATING_RANGE, RATING_RANGE, RATING_RANGE, RATING_RANGE];
+        self.count_accepted(ratings, &Target::Workflow(START_WORKFLOW.to_string()))
+    }
+}
+
+pub fn day_19_part_1() {
+    let system = get_input(false).parse::<System>().unwrap();
+    println!("Sum of accepted ratings: {}", system.sum_accepted_ratings());
+}
+
+pub fn day_19_part_2() {
+    let system = get_input(false).parse::<System>().unwrap();
+    println!("Accepted combinations: {}", system.count_all_accepted());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        px{a<2006:qkq,m>2090:A,rfg}
+        pv{a>1716:R,A}
+        lnx{m>1548:A,A}
+        rfg{s<537:gd,x>2440:R,A}
+        qs{s>3448:A,lnx}
+        qkq{x<1416:A,crn}
+        crn{x>2662:A,R}
+        in{s<1351:px,qqz}
+        qqz{s>2770:qs,m<1801:hdj,R}
+        gd{a>3333:R,R}
+        hdj{m>838:A,pv}
+
+        {x=787,m=2655,a=1222,s=2876}
+        {x=1679,m=44,a=2067,s=496}
+        {x=2036,m=264,a=79,s=2244}
+        {x=2461,m=1339,a=466,s=291}
+        {x=2127,m=1623,a=2188,s=1013}
+    */
+
+    #[test]
+    fn test_parse() {
+        let system = get_input(true).parse::<System>().unwrap();
+
+        assert_eq!(system.workflows.len(), 11);
+        assert_eq!(system.parts.len(), 5);
+        assert_eq!(system.parts[0].ratings, [787, 2655, 1222, 2876]);
+        assert_eq!(system.workflow("px").rules, vec![
+            Rule { condition: Some(Condition::LessThan(Category::A, 2006)), target: Target::Workflow("qkq".to_string()) },
+            Rule { condition: Some(Condition::GreaterThan(Category::M, 2090)), target: Target::Accept },
+            Rule { condition: None, target: Target::Workflow("rfg".to_string()) },
+        ]);
+    }
+
+    #[test]
+    fn test_is_accepted() {
+        let system = get_input(true).parse::<System>().unwrap();
+        let accepted = system.parts.iter().map(|x| system.is_accepted(x)).collect::<Vec<bool>>();
+
+        assert_eq!(accepted, vec![true, false, true, false, true]);
+        assert_eq!(system.sum_accepted_ratings(), 19114);
+    }
+
+    #[test]
+    fn test_split() {
+        let ratings: Ratings = [1..11, 1..11, 1..11, 1..11];
+
+        let (matching, rest) = Condition::LessThan(Category::M, 4).split(&ratings);
+        assert_eq!(matching.unwrap()[1], 1..4);
+        assert_eq!(rest.unwrap()[1], 4..11);
+
+        let (matching, rest) = Condition::GreaterThan(Category::S, 4).split(&ratings);
+        assert_eq!(matching.unwrap()[3], 5..11);
+        assert_eq!(rest.unwrap()[3], 1..5);
+
+        let (matching, rest) = Condition::GreaterThan(Category::X, 10).split(&ratings);
+        assert_eq!(matching, None);
+        assert_eq!(rest.unwrap()[0], 1..11);
+    }
+
+    #[test]
+    fn test_count_accepted() {
+        let system = get_input(true).parse::<System>().unwrap();
+
+        assert_eq!(system.count_all_accepted(), 167409079868000);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!("px{a<2006:qkq,m>2090:A}".parse::<Workflow>().is_err());
+        assert!("px{z<2006:qkq,A}".parse::<Workflow>().is_err());
+        assert!("{x=787,m=2655,q=1222,s=2876}".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn test_invalid_system() {
+        let part = "\n\n{x=1,m=1,a=1,s=1}";
+
+        assert!(format!("in{{x<5:in,in}}{}", part).parse::<System>().is_err());
+        assert!(format!("in{{x<5:px,A}}\npx{{m>3:R,qs}}\nqs{{in}}{}", part).parse::<System>().is_err());
+        assert!(format!("in{{x<5:px,A}}{}", part).parse::<System>().is_err());
+        assert!(format!("in{{x<5:A,R}}\nin{{R}}{}", part).parse::<System>().is_err());
+        assert!(format!("px{{x<5:A,R}}{}", part).parse::<System>().is_err());
+        assert!(format!("in{{x<5:px,qs}}\npx{{qs}}\nqs{{A}}{}", part).parse::<System>().is_ok());
+    }
+
+    #[test]
+    fn test_split_at_max() {
+        let ratings: Ratings = [1..11, 1..11, 1..11, 1..11];
+
+        let (matching, rest) = Condition::GreaterThan(Category::A, u64::MAX).split(&ratings);
+        assert_eq!(matching, None);
+        assert_eq!(rest.unwrap()[2], 1..11);
+    }
+}
@@ -1,11 +1,15 @@
 #![allow(dead_code)]
 
+use std::io::{self, BufRead};
+
+use crate::utils::parse::{invalid_data, open_input, read_records};
+
 const INPUT_PATH: &str = "src/inputs/day_2.txt";
 // const INPUT_PATH: &str = "src/inputs/day_2_test.txt";
 
-const MAX_RED: u32 = 12;
-const MAX_GREEN: u32 = 13;
-const MAX_BLUE: u32 = 14;
+pub(crate) const MAX_RED: u32 = 12;
+pub(crate) const MAX_GREEN: u32 = 13;
+pub(crate) const MAX_BLUE: u32 = 14;
 
 enum Color {
     Red,
@@ -24,74 +28,87 @@ impl Color {
 }
 
 pub fn day_2_part_1() {
-    let input = std::fs::read_to_string(INPUT_PATH).unwrap();
-    let games: Vec<&str> = input.split("\n").collect();
-    let mut total_possible_runs: u32 = 0;
-
-    for (i, game) in games.iter().enumerate() {
-        if play_game(game) {
-            total_possible_runs += (i + 1) as u32;
-        }
-    }
-
+    let total_possible_runs = sum_possible_games(open_input(INPUT_PATH).unwrap()).unwrap();
     println!("Total possible runs: {}", total_possible_runs);
 }
 
 pub fn day_2_part_2() {
-    let input = std::fs::read_to_string(INPUT_PATH).unwrap();
-    let games: Vec<&str> = input.split("\n").collect();
+    let total_power_of_cubes = sum_powers_of_cubes(open_input(INPUT_PATH).unwrap()).unwrap();
+    println!("Total power of cubes: {}", total_power_of_cubes);
+}
 
-    let mut total_power_of_cubes: u32 = 0;
+/// Sums the ids of the possible games, reading one game at a time.
+pub fn sum_possible_games<R: BufRead>(reader: R) -> io::Result<u64> {
+    let mut total_possible_runs: u64 = 0;
+    for game in read_records(reader) {
+        let game = game?;
+        let id = get_game_id(&game).ok_or_else(|| invalid_data(format!("Game must start with Game <id>: {}", game)))?;
+        if play_game(&game).ok_or_else(|| invalid_data(format!("Invalid game: {}", game)))? {
+            total_possible_runs += id as u64;
+        }
+    }
 
-    for game in games {
-        let (red, green, blue) = get_fewest_cubes(game);
-        let power_of_cubes = red * green * blue;
+    Ok(total_possible_runs)
+}
 
-        total_power_of_cubes += power_of_cubes;
+/// Sums the power of the fewest cubes needed for each game, reading one game at a time.
+pub fn sum_powers_of_cubes<R: BufRead>(reader: R) -> io::Result<u64> {
+    let mut total_power_of_cubes: u64 = 0;
+    for game in read_records(reader) {
+        let game = game?;
+        get_game_id(&game).ok_or_else(|| invalid_data(format!("Game must start with Game <id>: {}", game)))?;
+        let (red, green, blue) = get_fewest_cubes(&game).ok_or_else(|| invalid_data(format!("Invalid game: {}", game)))?;
+        total_power_of_cubes += red as u64 * green as u64 * blue as u64;
     }
 
-    println!("Total power of cubes: {}", total_power_of_cubes);
+    Ok(total_power_of_cubes)
 }
 
-fn play_game(game: &str) -> bool {
+fn get_game_id(game: &str) -> Option<u32> {
+    game.split_once(": ")
+        .and_then(|(x, _)| x.strip_prefix("Game "))
+        .and_then(|x| x.trim().parse::<u32>().ok())
+}
+
+/// Whether every run of the game is possible, or `None` if a run cannot be read.
+fn play_game(game: &str) -> Option<bool> {
     // take the part after the ":"
-    let runs_string: &str = game.split(": ").collect::<Vec<&str>>()[1];
+    let runs_string: &str = game.split_once(": ")?.1;
     let runs: Vec<&str> = runs_string.split("; ").collect();
 
     let mut possible_runs = Vec::new();
 
     for (i, run) in runs.iter().enumerate() {
         let run_string: &str = run.trim();
-        let (red, green, blue) = play_run(run_string);
+        let (red, green, blue) = play_run(run_string)?;
         if is_possible(red, green, blue) {
             possible_runs.push(i);
         } else {
-            return false;
+            return Some(false);
         }
     }
 
-    true
+    Some(true)
 }
 
-fn play_run(run: &str) -> (u32, u32, u32) {
+fn play_run(run: &str) -> Option<(u32, u32, u32)> {
     let colors: Vec<&str> = run.split(", ").collect();
 
-    (
-        get_cubes(&colors, Color::Red),
-        get_cubes(&colors, Color::Green),
-        get_cubes(&colors, Color::Blue)
-    )
+    Some((
+        get_cubes(&colors, Color::Red)?,
+        get_cubes(&colors, Color::Green)?,
+        get_cubes(&colors, Color::Blue)?
+    ))
 }
 
-fn get_cubes(colors: &Vec<&str>, color: Color) -> u32 {
+fn get_cubes(colors: &Vec<&str>, color: Color) -> Option<u32> {
     for c in colors {
         if c.contains(color.as_str()) {
-            let cube: u32 = c.split(" ").collect::<Vec<&str>>()[0].parse().unwrap();
-            return cube;
+            return c.split(" ").next()?.parse().ok();
         }
     }
 
-    0
+    Some(0)
 }
 
 fn is_possible(red: u32, green: u32, blue: u32) -> bool {
@@ -106,13 +123,13 @@ fn is_possible(red: u32, green: u32, blue: u32) -> bool {
     true
 }
 
-fn get_fewest_cubes(game: &str) -> (u32, u32, u32) {
-    let runs_string: &str = game.split(": ").collect::<Vec<&str>>()[1];
+fn get_fewest_cubes(game: &str) -> Option<(u32, u32, u32)> {
+    let runs_string: &str = game.split_once(": ")?.1;
     let runs: Vec<&str> = runs_string.split("; ").collect();
 
     let (mut min_red, mut min_green, mut min_blue) = (0, 0, 0);
     for run in runs {
-        let (red, green, blue) = play_run(run);
+        let (red, green, blue) = play_run(run)?;
 
         if red > min_red {
             min_red = red;
@@ -127,5 +144,46 @@ fn get_fewest_cubes(game: &str) -> (u32, u32, u32) {
         }
     }
 
-    (min_red, min_green, min_blue)
-}
\ No newline at end of file
+    Some((min_red, min_green, min_blue))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+        Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+        Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+        Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+    */
+
+    const INPUT: &str = include_str!("../../src/inputs/day_2_test.txt");
+
+    #[test]
+    fn test_sum_possible_games() {
+        assert_eq!(sum_possible_games(INPUT.as_bytes()).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_sum_powers_of_cubes() {
+        assert_eq!(sum_powers_of_cubes(INPUT.as_bytes()).unwrap(), 2286);
+    }
+
+    #[test]
+    fn test_get_game_id() {
+        assert_eq!(get_game_id("Game 42: 1 red"), Some(42));
+        assert_eq!(get_game_id("Gaem 42: 1 red"), None);
+    }
+
+    #[test]
+    fn test_invalid_games() {
+        for game in ["Game x: 1 red", "Game 1 1 red", "Game 1: x red", "Game 1: 1 red; red"] {
+            let input = format!("Game 1: 1 blue\n{}\n", game);
+
+            assert_eq!(sum_possible_games(input.as_bytes()).unwrap_err().kind(), io::ErrorKind::InvalidData, "{}", game);
+            assert_eq!(sum_powers_of_cubes(input.as_bytes()).unwrap_err().kind(), io::ErrorKind::InvalidData, "{}", game);
+        }
+    }
+}
@@ -0,0 +1,347 @@
+#![allow(dead_code)]
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_20_test.txt"),
+        false => include_str!("../../src/inputs/day_20.txt")
+    }
+}
+
+fn get_input_2(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_20_test_2.txt"),
+        false => include_str!("../../src/inputs/day_20.txt")
+    }
+}
+
+const BROADCASTER: &str = "broadcaster";
+const BUTTON: &str = "button";
+pub const TARGET: &str = "rx";
+pub const NB_PRESSES: usize = 1000;
+/// The feeders of the target are 12-bit counters in practice, so each one fires well within this many presses.
+pub const MAX_PRESSES: u64 = 1 << 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Signal {
+    from: String,
+    to: String,
+    pulse: Pulse,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Broadcaster,
+    FlipFlop { on: bool },
+    Conjunction { memory: HashMap<String, Pulse> },
+}
+
+#[derive(Debug, Clone)]
+struct Module {
+    name: String,
+    kind: Kind,
+    destinations: Vec<String>,
+}
+
+impl std::str::FromStr for Module {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, destinations) = s.trim()
+            .split_once(" -> ")
+            .ok_or(format!("Module must look like name -> destinations: {}", s))?;
+
+        let (name, kind) = if let Some(name) = name.strip_prefix('%') {
+            (name, Kind::FlipFlop { on: false })
+        } else if let Some(name) = name.strip_prefix('&') {
+            (name, Kind::Conjunction { memory: HashMap::new() })
+        } else if name == BROADCASTER {
+            (name, Kind::Broadcaster)
+        } else {
+            return Err(format!("Unknown module: {}", name).into());
+        };
+
+        let destinations = destinations.split(',')
+            .map(|x| x.trim().to_string())
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<String>>();
+
+        Ok(Module {
+            name: name.to_string(),
+            kind,
+            destinations,
+        })
+    }
+}
+
+impl Module {
+    /// Handles a pulse, and returns the pulse to send to every destination, if any.
+    fn receive(&mut self, signal: &Signal) -> Option<Pulse> {
+        match &mut self.kind {
+            Kind::Broadcaster => Some(signal.pulse),
+            Kind::FlipFlop { on } => match signal.pulse {
+                Pulse::High => None,
+                Pulse::Low => {
+                    *on = !*on;
+                    Some(if *on { Pulse::High } else { Pulse::Low })
+                }
+            },
+            Kind::Conjunction { memory } => {
+                memory.insert(signal.from.clone(), signal.pulse);
+                match memory.values().all(|x| *x == Pulse::High) {
+                    true => Some(Pulse::Low),
+                    false => Some(Pulse::High),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    modules: HashMap<String, Module>,
+}
+
+impl std::str::FromStr for Circuit {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modules: HashMap<String, Module> = HashMap::new();
+        for line in s.lines().filter(|x| !x.trim().is_empty()) {
+            let module = line.parse::<Module>()?;
+            modules.insert(module.name.clone(), module);
+        }
+
+        if !modules.contains_key(BROADCASTER) {
+            return Err("Circuit must have a broadcaster".into());
+        }
+
+        // Conjunctions remember a low pulse for each of their inputs at first
+        let links = modules.values()
+            .flat_map(|x| x.destinations.iter().map(|to| (x.name.clone(), to.clone())))
+            .collect::<Vec<(String, String)>>();
+        for (from, to) in links {
+            if let Some(Module { kind: Kind::Conjunction { memory }, .. }) = modules.get_mut(&to) {
+                memory.insert(from, Pulse::Low);
+            }
+        }
+
+        Ok(Circuit {
+            modules,
+        })
+    }
+}
+
+impl Circuit {
+    /// The names of the modules sending pulses to `name`.
+    fn inputs(&self, name: &str) -> Vec<String> {
+        let mut inputs = self.modules.values()
+            .filter(|x| x.destinations.iter().any(|to| to == name))
+            .map(|x| x.name.clone())
+            .collect::<Vec<String>>();
+        inputs.sort();
+
+        inputs
+    }
+
+    /// Presses the button once, handing every signal sent to `observe` in the order they are delivered.
+    fn press<F: FnMut(&Signal)>(&mut self, mut observe: F) {
+        let mut queue: VecDeque<Signal> = VecDeque::new();
+        queue.push_back(Signal {
+            from: BUTTON.to_string(),
+            to: BROADCASTER.to_string(),
+            pulse: Pulse::Low,
+        });
+
+        while let Some(signal) = queue.pop_front() {
+            observe(&signal);
+
+            // Modules without any outputs, like the target, only receive pulses
+            let Some(module) = self.modules.get_mut(&signal.to) else {
+                continue;
+            };
+
+            if let Some(pulse) = module.receive(&signal) {
+                for to in module.destinations.iter() {
+                    queue.push_back(Signal {
+                        from: module.name.clone(),
+                        to: to.clone(),
+                        pulse,
+                    });
+                }
+            }
+        }
+    }
+
+    /// The number of low pulses multiplied by the number of high pulses sent over `presses` presses.
+    pub fn pulse_product(&mut self, presses: usize) -> u64 {
+        let (mut low, mut high) = (0, 0);
+        for _ in 0..presses {
+            self.press(|signal| match signal.pulse {
+                Pulse::Low => low += 1,
+                Pulse::High => high += 1,
+            });
+        }
+
+        low * high
+    }
+
+    /// The first press at which `target` receives a low pulse.
+    ///
+    /// The target is fed by a single conjunction, which sends a low pulse once all its feeders sent it a
+    /// high pulse in the same press. Each feeder does so periodically, so the answer is the least common
+    /// multiple of the first press at which each feeder sends a high pulse. Gives up when a feeder has not
+    /// fired after `MAX_PRESSES` presses.
+    pub fn presses_until_low(&mut self, target: &str) -> Option<u64> {
+        let hubs = self.inputs(target);
+        let [hub] = hubs.as_slice() else {
+            return None;
+        };
+        if !matches!(self.modules[hub].kind, Kind::Conjunction { .. }) {
+            return None;
+        }
+
+        let mut cycles: HashMap<String, Option<u64>> = self.inputs(hub).into_iter().map(|x| (x, None)).collect();
+        let mut presses: u64 = 0;
+        while cycles.values().any(|x| x.is_none()) {
+            if presses == MAX_PRESSES {
+                return None;
+            }
+            presses += 1;
+            self.press(|signal| {
+                if signal.to == *hub && signal.pulse == Pulse::High {
+                    if let Some(cycle @ None) = cycles.get_mut(&signal.from) {
+                        *cycle = Some(presses);
+                    }
+                }
+            });
+        }
+
+        cycles.values().flatten().copied().reduce(num::integer::lcm)
+    }
+}
+
+pub fn day_20_part_1() {
+    let mut circuit = get_input(false).parse::<Circuit>().unwrap();
+    println!("Pulse product: {}", circuit.pulse_product(NB_PRESSES));
+}
+
+pub fn day_20_part_2() {
+    let mut circuit = get_input(false).parse::<Circuit>().unwrap();
+    println!("Presses until {} gets a low pulse: {}", TARGET, circuit.presses_until_low(TARGET).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        broadcaster -> a, b, c
+        %a -> b
+        %b -> c
+        %c -> inv
+        &inv -> a
+    */
+
+    /*
+        broadcaster -> a
+        %a -> inv, con
+        &inv -> b
+        %b -> con
+        &con -> output
+    */
+
+    /// Two counters that reset every 3 and every 5 presses, feeding `hub` through an inverter each.
+    const COUNTERS: &str = "broadcaster -> a1, c1
+%a1 -> a2, ca
+%a2 -> ca
+&ca -> ia, a1
+&ia -> hub
+%c1 -> c2, cc
+%c2 -> c3
+%c3 -> cc
+&cc -> ic, c1, c2
+&ic -> hub
+&hub -> rx";
+
+    #[test]
+    fn test_parse() {
+        let circuit = get_input_2(true).parse::<Circuit>().unwrap();
+
+        assert_eq!(circuit.modules.len(), 5);
+        assert_eq!(circuit.modules["a"].kind, Kind::FlipFlop { on: false });
+        assert_eq!(circuit.modules["a"].destinations, vec!["inv", "con"]);
+        assert_eq!(circuit.inputs("con"), vec!["a", "b"]);
+
+        match &circuit.modules["con"].kind {
+            Kind::Conjunction { memory } => {
+                assert_eq!(memory.len(), 2);
+                assert!(memory.values().all(|x| *x == Pulse::Low));
+            }
+            _ => panic!("con must be a conjunction"),
+        }
+    }
+
+    #[test]
+    fn test_press() {
+        let mut circuit = get_input(true).parse::<Circuit>().unwrap();
+        let mut signals: Vec<(String, Pulse, String)> = Vec::new();
+        circuit.press(|x| signals.push((x.from.clone(), x.pulse, x.to.clone())));
+
+        assert_eq!(signals.len(), 12);
+        assert_eq!(signals[0], ("button".to_string(), Pulse::Low, "broadcaster".to_string()));
+        assert_eq!(signals[4], ("a".to_string(), Pulse::High, "b".to_string()));
+        assert_eq!(signals[11], ("inv".to_string(), Pulse::High, "a".to_string()));
+    }
+
+    #[test]
+    fn test_pulse_product() {
+        let mut circuit = get_input(true).parse::<Circuit>().unwrap();
+        let mut other_circuit = get_input_2(true).parse::<Circuit>().unwrap();
+
+        assert_eq!(circuit.pulse_product(NB_PRESSES), 32000000);
+        assert_eq!(other_circuit.pulse_product(NB_PRESSES), 11687500);
+    }
+
+    #[test]
+    fn test_presses_until_low() {
+        let mut circuit = COUNTERS.parse::<Circuit>().unwrap();
+
+        assert_eq!(circuit.presses_until_low("rx"), Some(15));
+    }
+
+    #[test]
+    fn test_presses_until_low_without_high_pulse() {
+        let mut circuit = "broadcaster -> c\n&c -> f\n%f -> hub\n&hub -> rx".parse::<Circuit>().unwrap();
+
+        assert_eq!(circuit.presses_until_low("rx"), None);
+    }
+
+    #[test]
+    fn test_presses_until_low_matches_simulation() {
+        let mut circuit = COUNTERS.parse::<Circuit>().unwrap();
+        let mut presses = 0;
+        let mut received_low = false;
+        while !received_low {
+            presses += 1;
+            circuit.press(|x| received_low |= x.to == "rx" && x.pulse == Pulse::Low);
+        }
+
+        assert_eq!(presses, 15);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!("broadcaster a".parse::<Circuit>().is_err());
+        assert!("%a -> b".parse::<Circuit>().is_err());
+        assert!("broadcaster -> a\nfoo -> a".parse::<Circuit>().is_err());
+    }
+}
@@ -0,0 +1,295 @@
+#![allow(dead_code)]
+
+use std::collections::{HashSet, VecDeque};
+use std::error::Error;
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_21_test.txt"),
+        false => include_str!("../../src/inputs/day_21.txt")
+    }
+}
+
+pub const STEPS: usize = 64;
+pub const INFINITE_STEPS: usize = 26501365;
+
+const DIRECTIONS: [(i64, i64); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+#[derive(Debug)]
+pub struct Garden {
+    rocks: Vec<Vec<bool>>,
+    start: (usize, usize),
+}
+
+impl std::str::FromStr for Garden {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rocks: Vec<Vec<bool>> = Vec::new();
+        let mut start: Option<(usize, usize)> = None;
+
+        for (y, line) in s.lines().map(|x| x.trim()).filter(|x| !x.is_empty()).enumerate() {
+            let mut row: Vec<bool> = Vec::new();
+            for (x, character) in line.chars().enumerate() {
+                match character {
+                    '#' => row.push(true),
+                    '.' => row.push(false),
+                    'S' if start.is_none() => {
+                        start = Some((x, y));
+                        row.push(false);
+                    }
+                    'S' => return Err("Garden must have a single start".into()),
+                    _ => return Err(format!("Unknown tile: {}", character).into()),
+                }
+            }
+            rocks.push(row);
+        }
+
+        if rocks.iter().any(|x| x.len() != rocks[0].len()) {
+            return Err("All the lines of the garden must have the same width".into());
+        }
+
+        Ok(Garden {
+            rocks,
+            start: start.ok_or("Garden must have a start")?,
+        })
+    }
+}
+
+impl Garden {
+    fn height(&self) -> usize {
+        self.rocks.len()
+    }
+
+    fn width(&self) -> usize {
+        self.rocks.first().map_or(0, |x| x.len())
+    }
+
+    /// Whether there is a rock at the position, on the garden repeated infinitely in every direction.
+    fn is_rock(&self, x: i64, y: i64) -> bool {
+        let x = x.rem_euclid(self.width() as i64) as usize;
+        let y = y.rem_euclid(self.height() as i64) as usize;
+        self.rocks[y][x]
+    }
+
+    /// The number of steps to reach each plot from the start, or `None` if it is out of reach.
+    fn distances(&self) -> Vec<Vec<Option<usize>>> {
+        let mut distances: Vec<Vec<Option<usize>>> = vec![vec![None; self.width()]; self.height()];
+        let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+
+        distances[self.start.1][self.start.0] = Some(0);
+        queue.push_back(self.start);
+
+        while let Some((x, y)) = queue.pop_front() {
+            let distance = distances[y][x].unwrap();
+            for (dx, dy) in DIRECTIONS {
+                let (Some(next_x), Some(next_y)) = (x.checked_add_signed(dx as isize), y.checked_add_signed(dy as isize)) else {
+                    continue;
+                };
+                if next_x >= self.width() || next_y >= self.height() || self.rocks[next_y][next_x] {
+                    continue;
+                }
+                if distances[next_y][next_x].is_none() {
+                    distances[next_y][next_x] = Some(distance + 1);
+                    queue.push_back((next_x, next_y));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Plots reachable in exactly `steps` steps: the ones reachable in fewer steps of the same parity,
+    /// since the elf can always go back and forth.
+    pub fn reachable(&self, steps: usize) -> usize {
+        self.distances()
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|x| **x <= steps && **x % 2 == steps % 2)
+            .count()
+    }
+
+    /// Same as `reachable`, on the garden repeated infinitely, by walking every step.
+    fn reachable_infinite(&self, steps: usize) -> usize {
+        let start = (self.start.0 as i64, self.start.1 as i64);
+        let mut seen: HashSet<(i64, i64)> = HashSet::from([start]);
+        let mut frontier: Vec<(i64, i64)> = vec![start];
+        let mut count = steps.is_multiple_of(2) as usize;
+
+        for step in 1..=steps {
+            let mut next_frontier: Vec<(i64, i64)> = Vec::new();
+            for (x, y) in frontier {
+                for (dx, dy) in DIRECTIONS {
+                    let next = (x + dx, y + dy);
+                    if !self.is_rock(next.0, next.1) && seen.insert(next) {
+                        next_frontier.push(next);
+                    }
+                }
+            }
+
+            if step % 2 == steps % 2 {
+                count += next_frontier.len();
+            }
+            frontier = next_frontier;
+        }
+
+        count
+    }
+
+    /// Whether the garden is square, with the start in its middle, and with no rock on the row and the column of the
+    /// start nor on the edges, as in the real inputs.
+    fn has_clear_lanes(&self) -> bool {
+        let size = self.width();
+        let (x, y) = self.start;
+        let is_clear_row = |row: usize| self.rocks[row].iter().all(|x| !x);
+        let is_clear_column = |column: usize| self.rocks.iter().all(|x| !x[column]);
+
+        size == self.height() && x == size / 2 && y == size / 2
+            && [0, y, size - 1].into_iter().all(is_clear_row)
+            && [0, x, size - 1].into_iter().all(is_clear_column)
+    }
+
+    /// Same as `reachable_infinite`, for a huge number of steps.
+    ///
+    /// On a garden with clear lanes, the reachable plots grow as a quadratic of the number of gardens crossed,
+    /// so the count is sampled for the same remainder after 0, 1 and 2 garden widths, then extrapolated. Other
+    /// gardens do not grow that way, so they give an error past 2 garden widths.
+    pub fn reachable_extrapolated(&self, steps: usize) -> Result<u64, Box<dyn Error>> {
+        let size = self.width();
+        let remainder = steps % size;
+        let gardens = (steps / size) as u64;
+        if gardens < 3 {
+            return Ok(self.reachable_infinite(steps) as u64);
+        }
+
+        if !self.has_clear_lanes() {
+            return Err("Cannot extrapolate: the garden must be square, with the start in its middle, and no rock on the row and the column of the start nor on the edges".into());
+        }
+
+        let samples = [0, 1, 2].map(|x| self.reachable_infinite(remainder + x * size) as u64);
+        Ok(extrapolate_quadratic(samples, gardens).ok_or("Extrapolated count does not fit in u64")?)
+    }
+}
+
+/// Evaluates at `n` the quadratic going through `samples` at 0, 1 and 2, with Newton's forward differences.
+/// Gives nothing when the value does not fit in u64.
+fn extrapolate_quadratic(samples: [u64; 3], n: u64) -> Option<u64> {
+    let [y0, y1, y2] = samples.map(|x| x as i128);
+    let n = n as i128;
+
+    let first_difference = y1 - y0;
+    let second_difference = y2 - 2 * y1 + y0;
+    let value = n.checked_mul(first_difference)?
+        .checked_add(n.checked_mul(n - 1)? / 2 * second_difference)?
+        .checked_add(y0)?;
+
+    u64::try_from(value).ok()
+}
+
+pub fn day_21_part_1() {
+    let garden = get_input(false).parse::<Garden>().unwrap();
+    println!("Reachable plots: {}", garden.reachable(STEPS));
+}
+
+pub fn day_21_part_2() {
+    let garden = get_input(false).parse::<Garden>().unwrap();
+    println!("Reachable plots: {}", garden.reachable_extrapolated(INFINITE_STEPS).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        ...........
+        .....###.#.
+        .###.##..#.
+        ..#.#...#..
+        ....#.#....
+        .##..S####.
+        .##..#...#.
+        .......##..
+        .##.#.####.
+        .##..##.##.
+        ...........
+    */
+
+    /// A garden shaped like the real inputs: the row and column of the start, and the edges, are clear.
+    const CLEAR_LANES: &str = "...........
+.#.......#.
+...#...#...
+..#.....#..
+.#.......#.
+.....S.....
+.#.#.....#.
+...#....#..
+.#.....#...
+...#.....#.
+...........";
+
+    #[test]
+    fn test_parse() {
+        let garden = get_input(true).parse::<Garden>().unwrap();
+
+        assert_eq!(garden.start, (5, 5));
+        assert_eq!(garden.width(), 11);
+        assert!(garden.rocks[1][5]);
+        assert!(!garden.rocks[5][5]);
+    }
+
+    #[test]
+    fn test_reachable() {
+        let garden = get_input(true).parse::<Garden>().unwrap();
+
+        assert_eq!(garden.reachable(1), 2);
+        assert_eq!(garden.reachable(2), 4);
+        assert_eq!(garden.reachable(6), 16);
+    }
+
+    #[test]
+    fn test_reachable_infinite() {
+        let garden = get_input(true).parse::<Garden>().unwrap();
+
+        assert_eq!(garden.reachable_infinite(6), 16);
+        assert_eq!(garden.reachable_infinite(10), 50);
+        assert_eq!(garden.reachable_infinite(50), 1594);
+        assert_eq!(garden.reachable_infinite(100), 6536);
+    }
+
+    #[test]
+    fn test_extrapolate_quadratic() {
+        // n² + 2n + 3
+        assert_eq!(extrapolate_quadratic([3, 6, 11], 10), Some(123));
+        assert_eq!(extrapolate_quadratic([3, 6, 11], 2), Some(11));
+        assert_eq!(extrapolate_quadratic([3, 2, 0], 10), None);
+    }
+
+    #[test]
+    fn test_reachable_extrapolated_matches_brute_force() {
+        let garden = CLEAR_LANES.parse::<Garden>().unwrap();
+
+        for steps in [5, 16, 27, 38, 49, 60, 71, 40, 51, 62, 73] {
+            assert_eq!(garden.reachable_extrapolated(steps).unwrap(), garden.reachable_infinite(steps) as u64, "{} steps", steps);
+        }
+    }
+
+    #[test]
+    fn test_reachable_extrapolated_without_clear_lanes() {
+        let garden = get_input(true).parse::<Garden>().unwrap();
+        let wide = "..S..".parse::<Garden>().unwrap();
+
+        assert!(CLEAR_LANES.parse::<Garden>().unwrap().has_clear_lanes());
+        assert!(!garden.has_clear_lanes());
+        assert_eq!(garden.reachable_extrapolated(10).unwrap(), 50);
+        assert!(garden.reachable_extrapolated(100).is_err());
+        assert!(wide.reachable_extrapolated(100).is_err());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!("..\n.#".parse::<Garden>().is_err());
+        assert!("S.\nS#".parse::<Garden>().is_err());
+        assert!("S.\n.x".parse::<Garden>().is_err());
+    }
+}
@@ -0,0 +1,208 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::utils::vector::Vector3;
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_22_test.txt"),
+        false => include_str!("../../src/inputs/day_22.txt")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Brick {
+    start: Vector3,
+    end: Vector3,
+}
+
+impl std::str::FromStr for Brick {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.trim()
+            .split_once('~')
+            .ok_or(format!("Brick must look like x,y,z~x,y,z: {}", s))?;
+        let (start, end) = (start.parse::<Vector3>()?, end.parse::<Vector3>()?);
+
+        // Keep the start as the lowest corner, so the brick spans start..=end on every axis
+        Ok(Brick {
+            start: Vector3 { x: start.x.min(end.x), y: start.y.min(end.y), z: start.z.min(end.z) },
+            end: Vector3 { x: start.x.max(end.x), y: start.y.max(end.y), z: start.z.max(end.z) },
+        })
+    }
+}
+
+impl Brick {
+    fn footprint(&self) -> Vec<(i32, i32)> {
+        (self.start.x..=self.end.x)
+            .flat_map(|x| (self.start.y..=self.end.y).map(move |y| (x, y)))
+            .collect()
+    }
+
+    fn height(&self) -> i32 {
+        self.end.z - self.start.z + 1
+    }
+}
+
+/// The bricks once they all fell, with which bricks hold which.
+#[derive(Debug)]
+pub struct Stack {
+    bricks: Vec<Brick>,
+    supports: Vec<Vec<usize>>,
+    supported_by: Vec<Vec<usize>>,
+}
+
+impl Stack {
+    /// Lets the bricks fall from the lowest to the highest, keeping the top of the pile at each (x, y).
+    pub fn settle(mut bricks: Vec<Brick>) -> Stack {
+        bricks.sort_by_key(|x| x.start.z);
+
+        let mut height_map: HashMap<(i32, i32), (i32, usize)> = HashMap::new();
+        let mut supports: Vec<Vec<usize>> = vec![Vec::new(); bricks.len()];
+        let mut supported_by: Vec<Vec<usize>> = vec![Vec::new(); bricks.len()];
+
+        for (index, brick) in bricks.iter_mut().enumerate() {
+            let footprint = brick.footprint();
+            let below = footprint.iter().filter_map(|x| height_map.get(x)).collect::<Vec<_>>();
+            let top = below.iter().map(|(z, _)| *z).max().unwrap_or(0);
+
+            let mut holders = below.iter()
+                .filter(|(z, _)| *z == top)
+                .map(|(_, holder)| *holder)
+                .collect::<Vec<usize>>();
+            holders.sort();
+            holders.dedup();
+
+            for holder in holders.iter() {
+                supports[*holder].push(index);
+            }
+            supported_by[index] = holders;
+
+            let height = brick.height();
+            brick.start.z = top + 1;
+            brick.end.z = top + height;
+
+            for position in footprint {
+                height_map.insert(position, (brick.end.z, index));
+            }
+        }
+
+        Stack {
+            bricks,
+            supports,
+            supported_by,
+        }
+    }
+
+    /// A brick can be removed if every brick it holds is also held by another one.
+    fn is_removable(&self, brick: usize) -> bool {
+        self.supports[brick].iter().all(|x| self.supported_by[*x].len() > 1)
+    }
+
+    pub fn count_removable(&self) -> usize {
+        (0..self.bricks.len()).filter(|x| self.is_removable(*x)).count()
+    }
+
+    /// The number of other bricks that fall when the brick is removed.
+    fn chain_reaction(&self, brick: usize) -> usize {
+        let mut falling: Vec<bool> = vec![false; self.bricks.len()];
+        falling[brick] = true;
+
+        // Bricks are sorted from the lowest, so every holder is decided before the bricks it holds
+        let mut count = 0;
+        for index in brick + 1..self.bricks.len() {
+            let holders = &self.supported_by[index];
+            if !holders.is_empty() && holders.iter().all(|x| falling[*x]) {
+                falling[index] = true;
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    pub fn sum_chain_reactions(&self) -> usize {
+        (0..self.bricks.len()).map(|x| self.chain_reaction(x)).sum()
+    }
+}
+
+pub fn parse_bricks(input: &str) -> Result<Vec<Brick>, Box<dyn Error>> {
+    input
+        .lines()
+        .filter(|x| !x.trim().is_empty())
+        .map(|x| x.parse::<Brick>())
+        .collect::<Result<Vec<Brick>, _>>()
+}
+
+pub fn day_22_part_1() {
+    let stack = Stack::settle(parse_bricks(get_input(false)).unwrap());
+    println!("Removable bricks: {}", stack.count_removable());
+}
+
+pub fn day_22_part_2() {
+    let stack = Stack::settle(parse_bricks(get_input(false)).unwrap());
+    println!("Sum of falling bricks: {}", stack.sum_chain_reactions());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        1,0,1~1,2,1   <- A
+        0,0,2~2,0,2   <- B
+        0,2,3~2,2,3   <- C
+        0,0,4~0,2,4   <- D
+        2,0,5~2,2,5   <- E
+        0,1,6~2,1,6   <- F
+        1,1,8~1,1,9   <- G
+    */
+
+    #[test]
+    fn test_parse() {
+        let bricks = parse_bricks(get_input(true)).unwrap();
+
+        assert_eq!(bricks.len(), 7);
+        assert_eq!(bricks[0].start, Vector3 { x: 1, y: 0, z: 1 });
+        assert_eq!(bricks[0].end, Vector3 { x: 1, y: 2, z: 1 });
+        assert_eq!(bricks[6].height(), 2);
+        assert_eq!("2,2,2~0,0,1".parse::<Brick>().unwrap().start, Vector3 { x: 0, y: 0, z: 1 });
+    }
+
+    #[test]
+    fn test_settle() {
+        let stack = Stack::settle(parse_bricks(get_input(true)).unwrap());
+        let bottoms = stack.bricks.iter().map(|x| x.start.z).collect::<Vec<i32>>();
+
+        assert_eq!(bottoms, vec![1, 2, 2, 3, 3, 4, 5]);
+        assert_eq!(stack.supports[0], vec![1, 2]);
+        assert_eq!(stack.supported_by[3], vec![1, 2]);
+        assert_eq!(stack.supported_by[6], vec![5]);
+    }
+
+    #[test]
+    fn test_count_removable() {
+        let stack = Stack::settle(parse_bricks(get_input(true)).unwrap());
+
+        assert_eq!(stack.count_removable(), 5);
+    }
+
+    #[test]
+    fn test_chain_reaction() {
+        let stack = Stack::settle(parse_bricks(get_input(true)).unwrap());
+
+        assert_eq!(stack.chain_reaction(0), 6);
+        assert_eq!(stack.chain_reaction(5), 1);
+        assert_eq!(stack.sum_chain_reactions(), 7);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!("1,0,1-1,2,1".parse::<Brick>().is_err());
+        assert!("1,0~1,2,1".parse::<Brick>().is_err());
+        assert!(parse_bricks("1,0,1~1,2,1\n1,0~1,2,1").is_err());
+    }
+}
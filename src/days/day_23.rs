@@ -0,0 +1,210 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+
+use crate::utils::graph::{compress_grid, Graph};
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_23_test.txt"),
+        false => include_str!("../../src/inputs/day_23.txt")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tile {
+    Path,
+    Forest,
+    Slope(i64, i64),
+}
+
+#[derive(Debug)]
+pub struct Trails {
+    tiles: Vec<Vec<Tile>>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+impl std::str::FromStr for Trails {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = s.lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .map(|line| line.chars().map(|c| match c {
+                '.' => Ok(Tile::Path),
+                '#' => Ok(Tile::Forest),
+                '^' => Ok(Tile::Slope(0, -1)),
+                'v' => Ok(Tile::Slope(0, 1)),
+                '<' => Ok(Tile::Slope(-1, 0)),
+                '>' => Ok(Tile::Slope(1, 0)),
+                _ => Err(format!("Unknown tile: {}", c)),
+            }).collect::<Result<Vec<Tile>, String>>())
+            .collect::<Result<Vec<Vec<Tile>>, String>>()?;
+
+        if tiles.iter().any(|x| x.len() != tiles[0].len()) {
+            return Err("All the lines of the map must have the same width".into());
+        }
+
+        let path_in = |row: &Vec<Tile>| row.iter().position(|x| *x == Tile::Path);
+        let start = tiles.first().and_then(path_in).ok_or("Top row must have a path")?;
+        let end = tiles.last().and_then(path_in).ok_or("Bottom row must have a path")?;
+
+        Ok(Trails {
+            start: (start, 0),
+            end: (end, tiles.len() - 1),
+            tiles,
+        })
+    }
+}
+
+impl Trails {
+    fn tile(&self, x: i64, y: i64) -> Tile {
+        if x < 0 || y < 0 {
+            return Tile::Forest;
+        }
+
+        self.tiles
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+            .unwrap_or(Tile::Forest)
+    }
+
+    /// The tiles one step away. On a slope the only way is downhill, unless slopes are ignored.
+    fn moves(&self, (x, y): (usize, usize), slippery: bool) -> Vec<(usize, usize)> {
+        let directions = match self.tile(x as i64, y as i64) {
+            Tile::Slope(dx, dy) if slippery => vec![(dx, dy)],
+            _ => vec![(0, -1), (0, 1), (-1, 0), (1, 0)],
+        };
+
+        directions
+            .into_iter()
+            .map(|(dx, dy)| (x as i64 + dx, y as i64 + dy))
+            .filter(|(next_x, next_y)| match self.tile(*next_x, *next_y) {
+                Tile::Forest => false,
+                // Walking up a slope is not allowed
+                Tile::Slope(dx, dy) if slippery => (dx, dy) != (x as i64 - next_x, y as i64 - next_y),
+                _ => true,
+            })
+            .map(|(next_x, next_y)| (next_x as usize, next_y as usize))
+            .collect()
+    }
+
+    /// The tiles where trails meet, whatever the slopes around them.
+    fn junctions(&self) -> Vec<(usize, usize)> {
+        (0..self.tiles.len())
+            .flat_map(|y| (0..self.tiles[y].len()).map(move |x| (x, y)))
+            .filter(|(x, y)| self.tiles[*y][*x] != Tile::Forest && self.moves((*x, *y), false).len() > 2)
+            .collect()
+    }
+
+    /// The junctions of the trails, with the start as node 0 and the end as node 1.
+    fn graph(&self, slippery: bool) -> Graph {
+        let mut keep = vec![self.start, self.end];
+        keep.extend(self.junctions());
+
+        let (graph, _) = compress_grid(&keep, |cell| self.moves(cell, slippery));
+        graph
+    }
+
+    pub fn longest_hike(&self, slippery: bool) -> Option<u64> {
+        longest_path(&self.graph(slippery), 0, 1)
+    }
+}
+
+/// The longest path from `from` to `to` that never visits a node twice, with the visited nodes as a bitmask.
+fn longest_path(graph: &Graph, from: usize, to: usize) -> Option<u64> {
+    assert!(graph.len() <= 64, "Error: graph has too many nodes for a 64 bits mask");
+
+    fn search(graph: &Graph, node: usize, to: usize, visited: u64) -> Option<u64> {
+        if node == to {
+            return Some(0);
+        }
+
+        graph.neighbours(node)
+            .iter()
+            .filter(|(next, _)| visited & (1 << next) == 0)
+            .filter_map(|(next, length)| search(graph, *next, to, visited | (1 << next)).map(|x| x + length))
+            .max()
+    }
+
+    search(graph, from, to, 1 << from)
+}
+
+pub fn day_23_part_1() {
+    let trails = get_input(false).parse::<Trails>().unwrap();
+    println!("Longest hike: {}", trails.longest_hike(true).unwrap());
+}
+
+pub fn day_23_part_2() {
+    let trails = get_input(false).parse::<Trails>().unwrap();
+    println!("Longest hike without slopes: {}", trails.longest_hike(false).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        #.#####################
+        #.......#########...###
+        #######.#########.#.###
+        ###.....#.>.>.###.#.###
+        ###v#####.#v#.###.#.###
+        ###.>...#.#.#.....#...#
+        ###v###.#.#.#########.#
+        ###...#.#.#.......#...#
+        #####.#.#.#######.#.###
+        #.....#.#.#.......#...#
+        #.#####.#.#.#########v#
+        #.#...#...#...###...>.#
+        #.#.#v#######v###.###v#
+        #...#.>.#...>.>.#.###.#
+        #####v#.#.###v#.#.###.#
+        #.....#...#...#.#.#...#
+        #.#########.###.#.#.###
+        #...###...#...#...#.###
+        ###.###.#.###v#####v###
+        #...#...#.#.>.>.#.>.###
+        #.###.###.#.###.#.#v###
+        #.....###...###...#...#
+        #####################.#
+    */
+
+    #[test]
+    fn test_parse() {
+        let trails = get_input(true).parse::<Trails>().unwrap();
+
+        assert_eq!(trails.start, (1, 0));
+        assert_eq!(trails.end, (21, 22));
+        assert_eq!(trails.tiles[3][10], Tile::Slope(1, 0));
+        assert_eq!(trails.tiles[4][3], Tile::Slope(0, 1));
+    }
+
+    #[test]
+    fn test_graph() {
+        let trails = get_input(true).parse::<Trails>().unwrap();
+
+        // Start, end and the 7 junctions
+        assert_eq!(trails.graph(true).len(), 9);
+        assert_eq!(trails.graph(false).len(), 9);
+        assert_eq!(trails.graph(true).neighbours(0).len(), 1);
+        assert_eq!(trails.graph(true).neighbours(0)[0].1, 15);
+    }
+
+    #[test]
+    fn test_longest_hike() {
+        let trails = get_input(true).parse::<Trails>().unwrap();
+
+        assert_eq!(trails.longest_hike(true), Some(94));
+    }
+
+    #[test]
+    fn test_longest_hike_without_slopes() {
+        let trails = get_input(true).parse::<Trails>().unwrap();
+
+        assert_eq!(trails.longest_hike(false), Some(154));
+    }
+}
@@ -0,0 +1,257 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+
+use num::{BigInt, BigRational, Signed, Zero};
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_24_test.txt"),
+        false => include_str!("../../src/inputs/day_24.txt")
+    }
+}
+
+pub const TEST_AREA: (i64, i64) = (200000000000000, 400000000000000);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hailstone {
+    position: [i64; 3],
+    velocity: [i64; 3],
+}
+
+fn parse_triple(s: &str) -> Result<[i64; 3], Box<dyn Error>> {
+    let values = s.split(',')
+        .map(|x| x.trim().parse::<i64>())
+        .collect::<Result<Vec<i64>, _>>()?;
+
+    match values[..] {
+        [x, y, z] => Ok([x, y, z]),
+        _ => Err(format!("Must have 3 coordinates: {}", s).into()),
+    }
+}
+
+impl std::str::FromStr for Hailstone {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (position, velocity) = s.split_once('@')
+            .ok_or(format!("Hailstone must look like px, py, pz @ vx, vy, vz: {}", s))?;
+
+        Ok(Hailstone {
+            position: parse_triple(position)?,
+            velocity: parse_triple(velocity)?,
+        })
+    }
+}
+
+fn big(value: i64) -> BigRational {
+    BigRational::from_integer(BigInt::from(value))
+}
+
+/// Where the paths of two hailstones cross in the x/y plane, if they cross in the future of both.
+fn intersect_2d(a: &Hailstone, b: &Hailstone) -> Option<(BigRational, BigRational)> {
+    let [ax, ay, _] = a.position.map(big);
+    let [avx, avy, _] = a.velocity.map(big);
+    let [bx, by, _] = b.position.map(big);
+    let [bvx, bvy, _] = b.velocity.map(big);
+
+    // Solve a + t * av = b + s * bv with Cramer's rule
+    let determinant = &avx * &bvy - &avy * &bvx;
+    if determinant.is_zero() {
+        return None;
+    }
+
+    let (dx, dy) = (&bx - &ax, &by - &ay);
+    let t = (&dx * &bvy - &dy * &bvx) / &determinant;
+    let s = (&dx * &avy - &dy * &avx) / &determinant;
+    if t.is_negative() || s.is_negative() {
+        return None;
+    }
+
+    Some((ax + &t * avx, ay + &t * avy))
+}
+
+pub fn count_intersections(hailstones: &[Hailstone], area: (i64, i64)) -> usize {
+    let (min, max) = (big(area.0), big(area.1));
+    let inside = |x: &BigRational| *x >= min && *x <= max;
+
+    let mut count = 0;
+    for (i, a) in hailstones.iter().enumerate() {
+        for b in hailstones[i + 1..].iter() {
+            if let Some((x, y)) = intersect_2d(a, b) {
+                if inside(&x) && inside(&y) {
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    count
+}
+
+fn cross(a: &[BigRational; 3], b: &[BigRational; 3]) -> [BigRational; 3] {
+    [
+        &a[1] * &b[2] - &a[2] * &b[1],
+        &a[2] * &b[0] - &a[0] * &b[2],
+        &a[0] * &b[1] - &a[1] * &b[0],
+    ]
+}
+
+/// Solves the square system `matrix * x = rhs` with Gaussian elimination, or `None` if it is singular.
+fn solve(mut matrix: Vec<Vec<BigRational>>, mut rhs: Vec<BigRational>) -> Option<Vec<BigRational>> {
+    let size = rhs.len();
+
+    for column in 0..size {
+        let pivot = (column..size).find(|x| !matrix[*x][column].is_zero())?;
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+
+        let pivot_row = matrix[column].clone();
+        let pivot_rhs = rhs[column].clone();
+        for row in 0..size {
+            if row == column || matrix[row][column].is_zero() {
+                continue;
+            }
+
+            let factor = &matrix[row][column] / &pivot_row[column];
+            for (value, pivot_value) in matrix[row].iter_mut().zip(pivot_row.iter()).skip(column) {
+                *value -= &factor * pivot_value;
+            }
+            rhs[row] -= &factor * &pivot_rhs;
+        }
+    }
+
+    Some((0..size).map(|x| &rhs[x] / &matrix[x][x]).collect())
+}
+
+/// The position the rock must be thrown from to hit every hailstone.
+///
+/// For a rock at P with velocity V, each hailstone gives `(P - p) × (V - v) = 0`. The `P × V` term is
+/// the same for all of them, so subtracting the equations of two hailstones leaves a linear system:
+/// `P × (vj - vi) + (pj - pi) × V = pj × vj - pi × vi`. Two pairs give the 6 equations needed.
+pub fn rock_position(hailstones: &[Hailstone]) -> Option<[BigInt; 3]> {
+    let stones = hailstones
+        .iter()
+        .map(|x| (x.position.map(big), x.velocity.map(big)))
+        .collect::<Vec<_>>();
+
+    for i in 0..stones.len() {
+        for j in i + 1..stones.len() {
+            for k in j + 1..stones.len() {
+                let mut matrix: Vec<Vec<BigRational>> = Vec::new();
+                let mut rhs: Vec<BigRational> = Vec::new();
+
+                let (pi, vi) = &stones[i];
+                for (pj, vj) in [&stones[j], &stones[k]] {
+                    let w = [&vj[0] - &vi[0], &vj[1] - &vi[1], &vj[2] - &vi[2]];
+                    let u = [&pj[0] - &pi[0], &pj[1] - &pi[1], &pj[2] - &pi[2]];
+                    let zero = BigRational::zero();
+
+                    matrix.push(vec![zero.clone(), w[2].clone(), -&w[1], zero.clone(), -&u[2], u[1].clone()]);
+                    matrix.push(vec![-&w[2], zero.clone(), w[0].clone(), u[2].clone(), zero.clone(), -&u[0]]);
+                    matrix.push(vec![w[1].clone(), -&w[0], zero.clone(), -&u[1], u[0].clone(), zero.clone()]);
+
+                    let (a, b) = (cross(pj, vj), cross(pi, vi));
+                    rhs.extend((0..3).map(|x| &a[x] - &b[x]));
+                }
+
+                if let Some(solution) = solve(matrix, rhs) {
+                    if solution[..3].iter().all(|x| x.is_integer()) {
+                        return Some([0, 1, 2].map(|x| solution[x].to_integer()));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+pub fn parse_hailstones(input: &str) -> Result<Vec<Hailstone>, Box<dyn Error>> {
+    input
+        .lines()
+        .filter(|x| !x.trim().is_empty())
+        .map(|x| x.parse::<Hailstone>())
+        .collect::<Result<Vec<Hailstone>, _>>()
+}
+
+pub fn day_24_part_1() {
+    let hailstones = parse_hailstones(get_input(false)).unwrap();
+    println!("Intersections in test area: {}", count_intersections(&hailstones, TEST_AREA));
+}
+
+pub fn day_24_part_2() {
+    let hailstones = parse_hailstones(get_input(false)).unwrap();
+    let position = rock_position(&hailstones).unwrap();
+    let sum: BigInt = position.iter().sum();
+    println!("Sum of rock coordinates: {}", sum);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        19, 13, 30 @ -2,  1, -2
+        18, 19, 22 @ -1, -1, -2
+        20, 25, 34 @ -2, -2, -4
+        12, 31, 28 @ -1, -2, -1
+        20, 19, 15 @  1, -5, -3
+    */
+
+    #[test]
+    fn test_parse() {
+        let hailstones = parse_hailstones(get_input(true)).unwrap();
+
+        assert_eq!(hailstones.len(), 5);
+        assert_eq!(hailstones[0].position, [19, 13, 30]);
+        assert_eq!(hailstones[0].velocity, [-2, 1, -2]);
+        assert_eq!(hailstones[4].velocity, [1, -5, -3]);
+    }
+
+    #[test]
+    fn test_intersect_2d() {
+        let hailstones = parse_hailstones(get_input(true)).unwrap();
+        let rational = |numerator: i64, denominator: i64| BigRational::new(numerator.into(), denominator.into());
+
+        // Inside the test area, at x=14.333, y=15.333
+        assert_eq!(intersect_2d(&hailstones[0], &hailstones[1]), Some((rational(43, 3), rational(46, 3))));
+        // In the past for hailstone A
+        assert_eq!(intersect_2d(&hailstones[0], &hailstones[4]), None);
+        // Parallel
+        assert_eq!(intersect_2d(&hailstones[1], &hailstones[2]), None);
+    }
+
+    #[test]
+    fn test_count_intersections() {
+        let hailstones = parse_hailstones(get_input(true)).unwrap();
+
+        assert_eq!(count_intersections(&hailstones, (7, 27)), 2);
+    }
+
+    #[test]
+    fn test_exact_intersections_at_edge_of_area() {
+        let a = "399999999999997, 300000000000000, 0 @ 1, 0, 0".parse::<Hailstone>().unwrap();
+        let b = "400000000000000, 299999999999993, 0 @ 0, 1, 0".parse::<Hailstone>().unwrap();
+        let c = "400000000000000, 299999999999998, 0 @ 1, 3, 0".parse::<Hailstone>().unwrap();
+        let rational = |numerator: i64, denominator: i64| BigRational::new(numerator.into(), denominator.into());
+
+        // Exactly on the edge of the area
+        assert_eq!(intersect_2d(&a, &b), Some((big(400000000000000), big(300000000000000))));
+        // Two thirds past the edge of the area
+        assert_eq!(intersect_2d(&a, &c), Some((rational(1200000000000002, 3), big(300000000000000))));
+        assert_eq!(count_intersections(&[a, b, c], TEST_AREA), 2);
+    }
+
+    #[test]
+    fn test_rock_position() {
+        let hailstones = parse_hailstones(get_input(true)).unwrap();
+
+        assert_eq!(rock_position(&hailstones), Some([24, 13, 10].map(BigInt::from)));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(parse_hailstones("19, 13, 30 @ -2,  1, -2\n18, 19 @ -1, -1, -2").is_err());
+    }
+}
@@ -0,0 +1,110 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::utils::graph::Graph;
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day_25_test.txt"),
+        false => include_str!("../../src/inputs/day_25.txt")
+    }
+}
+
+pub const WIRES_TO_CUT: u64 = 3;
+
+#[derive(Debug)]
+pub struct Wiring {
+    names: Vec<String>,
+    graph: Graph,
+}
+
+impl std::str::FromStr for Wiring {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut names: Vec<String> = Vec::new();
+        let mut indexes: HashMap<String, usize> = HashMap::new();
+        let mut graph = Graph::new(0);
+
+        let mut index_of = |name: &str, graph: &mut Graph| -> usize {
+            *indexes.entry(name.to_string()).or_insert_with(|| {
+                names.push(name.to_string());
+                graph.add_node()
+            })
+        };
+
+        for line in s.lines().filter(|x| !x.trim().is_empty()) {
+            let (component, others) = line.split_once(':')
+                .ok_or(format!("Line must look like name: names: {}", line))?;
+
+            let from = index_of(component.trim(), &mut graph);
+            for other in others.split_whitespace() {
+                let to = index_of(other, &mut graph);
+                graph.add_undirected_edge(from, to, 1);
+            }
+        }
+
+        Ok(Wiring {
+            names,
+            graph,
+        })
+    }
+}
+
+impl Wiring {
+    /// Cuts the fewest wires to split the components in two groups, and multiplies the sizes of the groups.
+    pub fn split_groups(&self) -> Option<(u64, usize)> {
+        let (wires, group) = self.graph.minimum_cut()?;
+        Some((wires, group.len() * (self.graph.len() - group.len())))
+    }
+}
+
+pub fn day_25_part_1() {
+    let wiring = get_input(false).parse::<Wiring>().unwrap();
+    let (wires, product) = wiring.split_groups().unwrap();
+    if wires != WIRES_TO_CUT {
+        panic!("Error: expected to cut {} wires, but the minimum cut is {}", WIRES_TO_CUT, wires);
+    }
+
+    println!("Product of group sizes: {}", product);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        jqt: rhn xhk nvd
+        rsh: frs pzl lsr
+        xhk: hfx
+        cmg: qnr nvd lhk bvb
+        rhn: xhk bvb hfx
+        bvb: xhk hfx
+        pzl: lsr hfx nvd
+        qnr: nvd
+        ntq: jqt hfx bvb xhk
+        nvd: lhk
+        lsr: lhk
+        rzs: qnr cmg lsr rsh
+        frs: qnr lhk lsr
+    */
+
+    #[test]
+    fn test_parse() {
+        let wiring = get_input(true).parse::<Wiring>().unwrap();
+        let jqt = wiring.names.iter().position(|x| x == "jqt").unwrap();
+
+        assert_eq!(wiring.names.len(), 15);
+        assert_eq!(wiring.graph.len(), 15);
+        assert_eq!(wiring.graph.neighbours(jqt).len(), 4);
+    }
+
+    #[test]
+    fn test_split_groups() {
+        let wiring = get_input(true).parse::<Wiring>().unwrap();
+
+        assert_eq!(wiring.split_groups(), Some((WIRES_TO_CUT, 54)));
+    }
+}
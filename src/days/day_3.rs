@@ -1,15 +1,16 @@
 #![allow(dead_code)]
 
+use std::collections::HashMap;
+
+use crate::utils::vector::Vector;
+
 const INPUT_PATH: &str = "src/inputs/day_3.txt";
 // const INPUT_PATH: &str = "src/inputs/day_3_test.txt";
 
-
-#[derive(Clone)]
-#[derive(PartialEq)]
-struct Vector {
-    x: i32,
-    y: i32,
-}
+const PART_NUMBER_COLOR: &str = "\x1b[32m";
+const OTHER_NUMBER_COLOR: &str = "\x1b[31m";
+const GEAR_COLOR: &str = "\x1b[1;33m";
+const RESET_COLOR: &str = "\x1b[0m";
 
 #[derive(Clone)]
 struct Number {
@@ -26,53 +27,36 @@ struct Gear {
     numbers: Vec<i32>,
 }
 
+#[allow(clippy::enum_variant_names)]
 enum Tile {
     NumberTile(Number),
     SymbolTile(Symbol),
     GearTile(Gear),
 }
 
+#[derive(Default)]
 struct Map {
     tiles: Vec<Tile>,
+    /// The index in `tiles` of the tile at each position, so a tile is found without going through all of them.
+    positions: HashMap<(i32, i32), usize>,
 }
 
 impl Map {
-    pub fn get_tile_at_position(&self, position: Vector) -> Option<&Tile> { // Added pub to make function visible. The function return a reference instead of owned Tile
-        for tile in &self.tiles { // &self.tiles because you only need to borrow self.tiles not own it
-            match tile {
-                Tile::NumberTile(number) => {
-                    if number.position == position {
-                        return Some(&tile); // &tile because you're only borrowing tile
-                    }
-                }
-                Tile::SymbolTile(symbol) => {
-                    if symbol.position == position {
-                        return Some(&tile); // &tile because you're only borrowing tile
-                    }
-                }
-                Tile::GearTile(gear) => {
-                    if gear.position == position {
-                        return Some(&tile); // &tile because you're only borrowing tile
-                    }
-                }
-            }
-        }
-        None
+    fn get_tile_at_position(&self, position: Vector) -> Option<&Tile> { // The function return a reference instead of owned Tile
+        self.positions.get(&(position.x, position.y)).map(|x| &self.tiles[*x])
     }
 
     fn get_adjacent_positions(position: Vector) -> Vec<Vector> {
-        let mut adjacent_positions: Vec<Vector> = Vec::new();
-
-        adjacent_positions.push(Vector { x: position.x - 1, y: position.y });       // LEFT
-        adjacent_positions.push(Vector { x: position.x + 1, y: position.y });       // RIGHT
-        adjacent_positions.push(Vector { x: position.x, y: position.y - 1 });       // UP
-        adjacent_positions.push(Vector { x: position.x, y: position.y + 1 });       // DOWN
-        adjacent_positions.push(Vector { x: position.x - 1, y: position.y - 1 });   // UP LEFT
-        adjacent_positions.push(Vector { x: position.x + 1, y: position.y - 1 });   // UP RIGHT
-        adjacent_positions.push(Vector { x: position.x - 1, y: position.y + 1 });   // DOWN LEFT
-        adjacent_positions.push(Vector { x: position.x + 1, y: position.y + 1 });   // DOWN RIGHT
-
-        adjacent_positions
+        vec![
+            Vector { x: position.x - 1, y: position.y },        // LEFT
+            Vector { x: position.x + 1, y: position.y },        // RIGHT
+            Vector { x: position.x, y: position.y - 1 },        // UP
+            Vector { x: position.x, y: position.y + 1 },        // DOWN
+            Vector { x: position.x - 1, y: position.y - 1 },    // UP LEFT
+            Vector { x: position.x + 1, y: position.y - 1 },    // UP RIGHT
+            Vector { x: position.x - 1, y: position.y + 1 },    // DOWN LEFT
+            Vector { x: position.x + 1, y: position.y + 1 },    // DOWN RIGHT
+        ]
     }
 
     fn is_adjacent_to_symbol(&self, position: Vector) -> bool {
@@ -94,53 +78,65 @@ impl Map {
 
         false
     }
+}
 
-    fn is_adjacent_to_too_number(&self, position: Vector) -> (bool, Vec<i32>) {
-        let adjacent_positions = Map::get_adjacent_positions(position);
-        let mut adjacent_numbers = Vec::new();
+impl Number {
+    fn is_adjacent_to_symbol(&self, map: &Map) -> bool {
+        map.is_adjacent_to_symbol(self.position.clone())
+    }
+}
 
-        for adjacent_position in adjacent_positions {
-            if let Some(tile) = self.get_tile_at_position(adjacent_position) {
-                match tile {
-                    Tile::NumberTile(number) => {
-                        let full_number = get_full_number(number.position.clone(), self);
-                        if !adjacent_numbers.contains(&full_number) {
-                            adjacent_numbers.push(full_number);
-                        }
-                    }
-                    _ => {}
+impl Gear {
+    /// The numbers adjacent to the gear. Two numbers with the same value are both kept.
+    fn adjacent_numbers(&self, map: &Map) -> Vec<i32> {
+        let mut first_positions: Vec<Vector> = Vec::new();
+        for position in Map::get_adjacent_positions(self.position.clone()) {
+            if let Some(Tile::NumberTile(_)) = map.get_tile_at_position(position.clone()) {
+                let first_position = get_first_digit_position(position, map);
+                if !first_positions.contains(&first_position) {
+                    first_positions.push(first_position);
                 }
             }
         }
 
-        (adjacent_numbers.len() > 1, adjacent_numbers)
+        first_positions.into_iter().map(|x| vec_to_num(get_number_parts(x, map))).collect()
     }
-}
 
-impl Number {
-    fn is_adjacent_to_symbol(&self, map: &Map) -> bool {
-        map.is_adjacent_to_symbol(self.position.clone())
+    /// The product of the two numbers adjacent to the gear, if exactly two are.
+    fn ratio(&self, map: &Map) -> Option<i64> {
+        match self.adjacent_numbers(map)[..] {
+            [first, second] => Some(first as i64 * second as i64),
+            _ => None,
+        }
     }
 }
 
-impl Gear {
-    fn is_adjacent_to_too_number(&self, map: &Map) -> (bool, Vec<i32>) {
-        map.is_adjacent_to_too_number(self.position.clone())
+fn parse_map(input: &str) -> Map {
+    let mut map = Map::default();
+    for (y, line) in input.lines().enumerate() {
+        for (x, character) in line.chars().enumerate() {
+            add_to_map(&mut map, character, x, y);
+        }
     }
+
+    map
 }
 
 fn add_to_map(map: &mut Map, character: char, x: usize, y: usize) {
     let position = Vector { x: x as i32, y: y as i32 };
-    if character.is_numeric() {
-        let number = Number { value: character.to_digit(10).unwrap() as i32, position };
-        map.tiles.push(Tile::NumberTile(number));
+    let key = (position.x, position.y);
+    let tile = if character.is_numeric() {
+        Tile::NumberTile(Number { value: character.to_digit(10).unwrap() as i32, position })
     } else if character == '*' {
-        let gear = Gear { position, numbers: [].to_vec() };
-        map.tiles.push(Tile::GearTile(gear));
+        Tile::GearTile(Gear { position, numbers: [].to_vec() })
     } else if character != '.' {
-        let symbol = Symbol { position };
-        map.tiles.push(Tile::SymbolTile(symbol));
-    }
+        Tile::SymbolTile(Symbol { position })
+    } else {
+        return;
+    };
+
+    map.positions.insert(key, map.tiles.len());
+    map.tiles.push(tile);
 }
 
 fn vec_to_num(vect: Vec<i32>) -> i32 {
@@ -152,8 +148,8 @@ fn vec_to_num(vect: Vec<i32>) -> i32 {
 }
 
 fn get_full_number(position: Vector, map: &Map) -> i32 {
-    let first_position = get_first_digit_position(position.clone(), &map);
-    let parts_of_number: Vec<i32> = get_number_parts(first_position.clone(), &map);
+    let first_position = get_first_digit_position(position.clone(), map);
+    let parts_of_number: Vec<i32> = get_number_parts(first_position.clone(), map);
     vec_to_num(parts_of_number)
 }
 
@@ -211,9 +207,18 @@ fn get_number_parts(position: Vector, map: &Map) -> Vec<i32> {
 
 pub fn day_3_part_1() {
     let input = std::fs::read_to_string(INPUT_PATH).unwrap();
+    println!("Sum of adjacent numbers: {}", sum_part_numbers(&input));
+}
 
+pub fn day_3_part_2() {
+    let input = std::fs::read_to_string(INPUT_PATH).unwrap();
+    println!("Sum of ratios: {}", sum_gear_ratios(&input));
+}
+
+/// Sums the numbers adjacent to a symbol, even diagonally.
+pub fn sum_part_numbers(input: &str) -> i32 {
     let lines = input.lines().enumerate();
-    let mut map = Map { tiles: Vec::new() };
+    let mut map = Map::default();
     let mut number_groups: Vec<Vec<Number>> = Vec::new();
     for (y, line) in lines {
         let chars = line.chars().enumerate();
@@ -227,14 +232,14 @@ pub fn day_3_part_1() {
                 };
                 num_group.push(number);
             } else {
-                if num_group.len() >= 1 {
+                if !num_group.is_empty() {
                     number_groups.push(num_group.clone());
                     num_group.clear();
                 }
             }
         }
 
-        if num_group.len() >= 1 {
+        if !num_group.is_empty() {
             number_groups.push(num_group.clone());
             num_group.clear();
         }
@@ -247,7 +252,7 @@ pub fn day_3_part_1() {
                 adjacent_numbers.push(number.clone());
             }
         }
-        adjacent_numbers.len() > 0
+        !adjacent_numbers.is_empty()
     }).cloned().collect();
 
     let mut sum_of_adjacent_numbers: i32 = 0;
@@ -261,57 +266,110 @@ pub fn day_3_part_1() {
         sum_of_adjacent_numbers += group_value;
     }
 
-    println!("Sum of adjacent numbers: {}", sum_of_adjacent_numbers);
+    sum_of_adjacent_numbers
 }
 
-pub fn day_3_part_2() {
-    let input = std::fs::read_to_string(INPUT_PATH).unwrap();
+/// Sums the ratios of the gears, the `*` adjacent to exactly two numbers.
+pub fn sum_gear_ratios(input: &str) -> i64 {
+    let map = parse_map(input);
 
-    let lines = input.lines().enumerate();
-    let mut map = Map { tiles: Vec::new() };
-    let mut gears: Vec<Gear> = Vec::new();
-    for (y, line) in lines {
-        let chars = line.chars().enumerate();
-        let mut num_group = Vec::new();
-        for (x, character) in chars {
-            add_to_map(&mut map, character, x, y);
-            if character.is_numeric() {
-                num_group.push(character.to_digit(10).unwrap() as i32);
-            } else {
-                if character == '*' {
-                    let gear = Gear {
-                        position: Vector { x: x as i32, y: y as i32 },
-                        numbers: [].to_vec(),
-                    };
-                    gears.push(gear);
+    map.tiles.iter()
+        .filter_map(|tile| match tile {
+            Tile::GearTile(gear) => gear.ratio(&map),
+            _ => None,
+        })
+        .sum()
+}
+
+/// Draws the schematic with the part numbers, the other numbers and the valid gears in their own colors, followed by
+/// the numbers that are not part numbers and the ratio of every valid gear. Without colors the schematic is drawn as is.
+pub fn render(input: &str, color: bool) -> String {
+    let map = parse_map(input);
+    let paint = |text: &str, code: &str| match color {
+        true => format!("{}{}{}", code, text, RESET_COLOR),
+        false => text.to_string(),
+    };
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut other_numbers: Vec<String> = Vec::new();
+    let mut gear_lines: Vec<String> = Vec::new();
+    for (y, line) in input.lines().enumerate() {
+        let characters = line.chars().collect::<Vec<char>>();
+        let mut rendered = String::new();
+        let mut x = 0;
+        while x < characters.len() {
+            match map.get_tile_at_position(Vector { x: x as i32, y: y as i32 }) {
+                Some(Tile::NumberTile(_)) => {
+                    let start = x;
+                    while x < characters.len() && characters[x].is_numeric() {
+                        x += 1;
+                    }
+                    let digits = characters[start..x].iter().collect::<String>();
+                    let is_part = (start..x).any(|x| map.is_adjacent_to_symbol(Vector { x: x as i32, y: y as i32 }));
+                    if is_part {
+                        rendered.push_str(&paint(&digits, PART_NUMBER_COLOR));
+                    } else {
+                        rendered.push_str(&paint(&digits, OTHER_NUMBER_COLOR));
+                        other_numbers.push(digits);
+                    }
+                    continue;
                 }
-                end_group(&mut num_group);
+                Some(Tile::GearTile(gear)) => match gear.ratio(&map) {
+                    Some(ratio) => {
+                        rendered.push_str(&paint("*", GEAR_COLOR));
+                        gear_lines.push(format!("Gear at {},{}: ratio {}", x, y, paint(&ratio.to_string(), GEAR_COLOR)));
+                    }
+                    None => rendered.push('*'),
+                },
+                _ => rendered.push(characters[x]),
             }
+            x += 1;
         }
-
-        end_group(&mut num_group);
+        lines.push(rendered);
     }
 
-    let mut ratios: Vec<i32> = Vec::new();
+    lines.push(String::new());
+    lines.push(format!("Not part numbers: {}", other_numbers.join(", ")));
+    lines.extend(gear_lines);
 
-    for gear in &mut gears {
-        let (is_adjacent, numbers) = gear.is_adjacent_to_too_number(&map);
-        if is_adjacent {
-            let mult = numbers.iter().fold(1, |acc, x| acc * x);
-            ratios.push(mult);
-        }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../src/inputs/day_3_test.txt");
+
+    #[test]
+    fn test_render_plain() {
+        let rendered = render(EXAMPLE, false);
+        let lines = rendered.lines().collect::<Vec<&str>>();
+
+        assert_eq!(lines[..10].join("\n"), EXAMPLE.trim_end());
+        assert_eq!(
+            lines[10..],
+            ["", "Not part numbers: 114, 58", "Gear at 3,1: ratio 16345", "Gear at 5,8: ratio 451490"]
+        );
     }
 
-    let mut sum_of_ratios = 0;
-    for ratio in ratios {
-        sum_of_ratios += ratio;
+    #[test]
+    fn test_render_colors() {
+        let rendered = render(EXAMPLE, true);
+
+        assert!(rendered.starts_with("\x1b[32m467\x1b[0m..\x1b[31m114\x1b[0m.."));
+        assert!(rendered.contains("617\x1b[0m*."));
+        assert!(rendered.contains("...\x1b[1;33m*\x1b[0m......"));
     }
 
-    println!("Sum of ratios: {}", sum_of_ratios);
-}
+    #[test]
+    fn test_gear_ratio_keeps_equal_numbers() {
+        let map = parse_map("12*12\n.....\n..*..");
+        let gears = map.tiles.iter().filter_map(|x| match x {
+            Tile::GearTile(gear) => Some(gear.ratio(&map)),
+            _ => None,
+        });
 
-fn end_group(num_group: &mut Vec<i32>) {
-    if num_group.len() >= 1 {
-        num_group.clear();
+        assert_eq!(gears.collect::<Vec<_>>(), vec![Some(144), None]);
     }
-}
\ No newline at end of file
+}
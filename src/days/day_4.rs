@@ -1,11 +1,18 @@
 #![allow(dead_code)]
 
-// const INPUT_PATH: &str = "src/inputs/day_4.txt";
-const INPUT_PATH: &str = "src/inputs/day_4_test.txt";
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+use std::error::Error;
+
+use crate::utils::parse::{invalid_data, open_input, read_records};
+
+const INPUT_PATH: &str = "src/inputs/day_4.txt";
+// const INPUT_PATH: &str = "src/inputs/day_4_test.txt";
 // const INPUT_PATH: &str = "src/inputs/day_4_test_2.txt";
 
 #[derive(Clone)]
-pub struct Card {
+struct Card {
     id: i32,
     raw_line: String,
     hand: Vec<i32>,
@@ -82,25 +89,25 @@ struct Card2
 
 impl std::str::FromStr for Card2
 {
-    type Err = ();
+    type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err>
     {
-        let mut parts = s.split(':');
-        let id = parts.next().unwrap().split(' ').next_back().unwrap().parse().unwrap();
-        let mut sets = parts.next().unwrap().split('|');
-        let winning = sets.next().unwrap()
+        let (header, sets) = s.split_once(':').ok_or(format!("Card must start with Card <id>: {}", s))?;
+        let id = header.strip_prefix("Card").ok_or(format!("Card must start with Card <id>: {}", s))?.trim().parse()?;
+        let (winning, hand) = sets.split_once('|').ok_or(format!("Card numbers must be split by '|': {}", s))?;
+        let winning = winning
             .trim()
             .split(' ')
             .filter(|x| !x.is_empty())
-            .map(|x| x.parse().unwrap())
-            .collect::<Vec<usize>>();
-        let hand = sets.next().unwrap()
+            .map(|x| x.parse())
+            .collect::<Result<Vec<usize>, _>>()?;
+        let hand = hand
             .trim()
             .split(' ')
             .filter(|x| !x.is_empty())
-            .map(|x| x.parse().unwrap())
-            .collect::<Vec<usize>>();
+            .map(|x| x.parse())
+            .collect::<Result<Vec<usize>, _>>()?;
         let matches = hand.iter().filter(|&x| winning.contains(x)).count();
         let points = 2_usize.pow(matches as u32) / 2;
 
@@ -124,40 +131,46 @@ fn get_indexes(length: i32, start_from: i32) -> Vec<i32> {
 }
 
 pub fn day_4_part_1() {
-    let input = std::fs::read_to_string(INPUT_PATH).unwrap();
-
-    let mut total_points = 0;
-    let mut index = 0;
-    for line in input.lines() {
-        index += 1;
-        let card = Card::new(index, line, 0);
-        let points = card.get_points();
-        total_points += points;
-    }
-
+    let total_points = total_points(open_input(INPUT_PATH).unwrap()).unwrap();
     println!("Total points: {}", total_points);
 }
 
 pub fn day_4_part_2() {
-    let cards = include_str!("../../src/inputs/day_4.txt")
-        .lines()
-        .map(|x| x.parse::<Card2>().unwrap())
-        .collect::<Vec<Card2>>();
+    let total_scratchcards = total_scratchcards(open_input(INPUT_PATH).unwrap()).unwrap();
+    println!("Total scratchcards: {}", total_scratchcards);
+}
 
-    let mut counters: Vec<usize> = vec![1; cards.len()];
+/// Sums the points of every card, reading one card at a time.
+pub fn total_points<R: BufRead>(reader: R) -> io::Result<u64> {
+    let mut total_points: u64 = 0;
+    for line in read_records(reader) {
+        let card = line?.parse::<Card2>().map_err(|x| invalid_data(x.to_string()))?;
+        total_points += card.points as u64;
+    }
 
-    for i in 0..counters.len() {
-        for j in i..i + cards[i].matches {
-            if j + 1 < counters.len() {
-                counters[j + 1] += counters[i];
-            }
+    Ok(total_points)
+}
+
+/// Counts the original and copied scratchcards, reading one card at a time.
+///
+/// A card only wins copies of the next few cards, so only the copies won for those are kept.
+pub fn total_scratchcards<R: BufRead>(reader: R) -> io::Result<u64> {
+    let mut won_copies: VecDeque<u64> = VecDeque::new();
+    let mut total_scratchcards: u64 = 0;
+    for line in read_records(reader) {
+        let card = line?.parse::<Card2>().map_err(|x| invalid_data(x.to_string()))?;
+        let copies = 1 + won_copies.pop_front().unwrap_or(0);
+        total_scratchcards += copies;
+
+        if won_copies.len() < card.matches {
+            won_copies.resize(card.matches, 0);
+        }
+        for next_card in won_copies.iter_mut().take(card.matches) {
+            *next_card += copies;
         }
     }
 
-    let result1: usize = cards.iter().map(|card| card.points).sum();
-    let result2: usize = counters.iter().sum();
-
-    println!("4\t{result1:<20}\t{result2:<20}");
+    Ok(total_scratchcards)
 }
 
 // #[cfg(test)]
@@ -221,4 +234,72 @@ pub fn day_4_part_2() {
 //
 //         assert_eq!(card_6.len(), 1);
 //     }
-// }
\ No newline at end of file
+// }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::day_4::{generate, oracle, Size};
+    use crate::utils::differential::{compare, shrink_lines};
+
+    /*
+        Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+        Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+        Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+        Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+        Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+    */
+
+    const INPUT: &str = include_str!("../../src/inputs/day_4_test.txt");
+
+    #[test]
+    fn test_total_points() {
+        assert_eq!(total_points(INPUT.as_bytes()).unwrap(), 13);
+    }
+
+    #[test]
+    fn test_total_scratchcards() {
+        assert_eq!(total_scratchcards(INPUT.as_bytes()).unwrap(), 30);
+    }
+
+    #[test]
+    fn test_invalid_cards() {
+        for card in ["Crad 2: 1 2 | 3", "Card x: 1 2 | 3", "Card 2 1 2 | 3", "Card 2: 1 2 3", "Card 2: 1 x | 3"] {
+            let input = format!("Card 1: 1 | 1\n{}\n", card);
+
+            assert_eq!(total_points(input.as_bytes()).unwrap_err().kind(), io::ErrorKind::InvalidData, "{}", card);
+            assert_eq!(total_scratchcards(input.as_bytes()).unwrap_err().kind(), io::ErrorKind::InvalidData, "{}", card);
+        }
+    }
+
+    #[test]
+    fn test_card_points_match_card_2_points() {
+        let result = compare(
+            0..200,
+            |seed| generate(seed, &Size::default()).input,
+            |x| shrink_lines(x, |_| true),
+            |x| x.lines().enumerate().map(|(i, line)| Card::new(i as i32 + 1, line, 0).get_points() as u64).collect::<Vec<u64>>(),
+            |x| x.lines().map(|line| line.parse::<Card2>().unwrap().points as u64).collect::<Vec<u64>>(),
+        );
+
+        if let Err(mismatch) = result {
+            panic!("{}", mismatch);
+        }
+    }
+
+    #[test]
+    fn test_total_scratchcards_matches_oracle() {
+        let result = compare(
+            0..200,
+            |seed| generate(seed, &Size::default()).input,
+            |x| shrink_lines(x, |_| true),
+            |x| oracle(x)[1],
+            |x| total_scratchcards(x.as_bytes()).unwrap(),
+        );
+
+        if let Err(mismatch) = result {
+            panic!("{}", mismatch);
+        }
+    }
+}
@@ -1,5 +1,13 @@
 #![allow(dead_code)]
 
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::ops::Range;
+
+use crate::utils::parse::{invalid_data, split_blocks};
+use crate::utils::range::{intersect, subtract};
+
 fn get_input(test_input: bool) -> &'static str {
     match test_input {
         true => include_str!("../../src/inputs/day_5_test.txt"),
@@ -7,6 +15,9 @@ fn get_input(test_input: bool) -> &'static str {
     }
 }
 
+const FIRST_CATEGORY: &str = "seed";
+const LAST_CATEGORY: &str = "location";
+
 #[derive(Debug)]
 struct Location {
     start_destination: u64,
@@ -15,64 +26,147 @@ struct Location {
 }
 
 #[derive(Debug)]
-struct Map {
+pub struct Map {
     from: String,
     to: String,
     value: Vec<Location>,
 }
 
 #[derive(Debug)]
-struct Seeds {
-    value: Vec<u64>,
-    ranges: Vec<(u64, u64)>,
+pub struct Seeds {
+    pub value: Vec<u64>,
+    pub ranges: Vec<(u64, u64)>,
 }
 
 #[derive(Debug)]
-struct Maps {
-    seeds: Seeds,
-    maps: Vec<Map>,
+pub struct Maps {
+    pub seeds: Seeds,
+    pub maps: Vec<Map>,
 }
 
+use std::error::Error;
+
 impl std::str::FromStr for Maps {
-    type Err = ();
+    type Err = Box<dyn Error>;
 
+    /// Parses the almanac, with its maps resolved from seeds to locations.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split("\n\r").collect::<Vec<&str>>();
+        let parts = split_blocks(s);
 
-        let seeds: Seeds = parts[0].parse::<Seeds>().unwrap();
+        let seeds: Seeds = parts.first().ok_or("Almanac has no seeds")?.parse::<Seeds>()?;
 
         let mut maps: Vec<Map> = Vec::new();
         for part in parts[1..].iter() {
-            let map = part.parse::<Map>().unwrap();
+            let map = part.parse::<Map>()?;
             maps.push(map);
         }
 
-        Ok(Maps {
+        Maps {
             seeds,
             maps,
-        })
+        }.resolve()
     }
 }
 
-use std::error::Error;
+impl Maps {
+    /// The maps keyed by the category they map from.
+    fn by_source(&self) -> HashMap<&str, &Map> {
+        self.maps.iter().map(|x| (x.from.as_str(), x)).collect()
+    }
+
+    /// The maps to apply one after another to go from one category to another, found by following the categories
+    /// each map leads to.
+    fn path(&self, from: &str, to: &str) -> Result<Vec<&Map>, Box<dyn Error>> {
+        let by_source = self.by_source();
+        let mut path: Vec<&Map> = Vec::new();
+        let mut category = from;
+
+        while category != to {
+            let map = *by_source.get(category).ok_or(format!("Missing link: no map from {} on the way to {}", category, to))?;
+            if path.iter().any(|x| x.from == map.from) {
+                return Err(format!("Cycle through {} on the way from {} to {}", category, from, to).into());
+            }
+            path.push(map);
+            category = &map.to;
+        }
+
+        Ok(path)
+    }
+
+    /// Checks that no two maps start from the same category, that the source ranges of each map are apart, that no
+    /// category leads back to itself, and that the seeds lead to the locations. Gives the maps from seeds to locations.
+    fn validate(&self) -> Result<Vec<&Map>, Box<dyn Error>> {
+        for (i, map) in self.maps.iter().enumerate() {
+            if let Some(other) = self.maps[..i].iter().find(|x| x.from == map.from) {
+                return Err(format!("Duplicate maps from {}: {}-to-{} and {}-to-{}", map.from, other.from, other.to, map.from, map.to).into());
+            }
+        }
+
+        for map in self.maps.iter() {
+            let mut sources = map.value.iter()
+                .map(|x| x.start_source..x.start_source + x.length)
+                .filter(|x| !x.is_empty())
+                .collect::<Vec<Range<u64>>>();
+            sources.sort_by_key(|x| x.start);
+            if let Some(pair) = sources.windows(2).find(|x| x[0].end > x[1].start) {
+                return Err(format!("Overlapping source ranges in the {}-to-{} map: {:?} and {:?}", map.from, map.to, pair[0], pair[1]).into());
+            }
+        }
+
+        let by_source = self.by_source();
+        for map in self.maps.iter() {
+            let mut seen: Vec<&str> = Vec::new();
+            let mut category = map.from.as_str();
+            while let Some(next) = by_source.get(category) {
+                if seen.contains(&category) {
+                    return Err(format!("Cycle through {}", category).into());
+                }
+                seen.push(category);
+                category = &next.to;
+            }
+        }
+
+        self.path(FIRST_CATEGORY, LAST_CATEGORY)
+    }
+
+    /// The almanac once validated, with its maps in the order that leads from seeds to locations. Maps off that path
+    /// are left out.
+    fn resolve(self) -> Result<Maps, Box<dyn Error>> {
+        let order = self.validate()?
+            .iter()
+            .map(|x| x.from.clone())
+            .collect::<Vec<String>>();
+
+        let mut maps = self.maps;
+        maps.retain(|x| order.contains(&x.from));
+        maps.sort_by_key(|x| order.iter().position(|y| *y == x.from));
+
+        Ok(Maps {
+            seeds: self.seeds,
+            maps,
+        })
+    }
+}
 
 impl std::str::FromStr for Seeds {
     type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value = s.split(":")
-            .nth(1)
-            .unwrap()
-            .trim()
+        let value = s.split_once(":")
+            .ok_or(format!("Seeds must follow 'seeds:': {}", s))?
+            .1
             .split_whitespace()
-            .map(|x| x.parse::<u64>().unwrap())
-            .collect::<Vec<u64>>();
+            .map(|x| x.parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>()?;
+
+        if value.len() % 2 != 0 {
+            return Err(format!("Seeds must come in pairs of start and length: {}", s).into());
+        }
 
         let mut ranges: Vec<(u64, u64)> = Vec::new();
-        for i in 0..value.len() {
-            if i % 2 == 0 {
-                ranges.push((value[i], value[i + 1]));
-            }
+        for pair in value.chunks(2) {
+            pair[0].checked_add(pair[1]).ok_or(format!("Seed range overflows: {} {}", pair[0], pair[1]))?;
+            ranges.push((pair[0], pair[1]));
         }
 
         Ok(Seeds {
@@ -82,40 +176,46 @@ impl std::str::FromStr for Seeds {
     }
 }
 
+impl std::str::FromStr for Location {
+    type Err = Box<dyn Error>;
+
+    /// Parses a line of a map, as the destination start, the source start and the length.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let numbers = s.split_whitespace()
+            .map(|x| x.parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>()?;
+
+        let [start_destination, start_source, length] = numbers[..] else {
+            return Err(format!("Map line must have 3 numbers: {}", s).into());
+        };
+        if start_destination.checked_add(length).is_none() || start_source.checked_add(length).is_none() {
+            return Err(format!("Map line overflows: {}", s).into());
+        }
+
+        Ok(Location {
+            start_destination,
+            start_source,
+            length,
+        })
+    }
+}
+
 impl std::str::FromStr for Map {
     type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let name = s.split(":")
-            .nth(0)
-            .unwrap()
-            .split_whitespace()
-            .nth(0)
-            .unwrap()
-            .split("-to-");
-
-        let value_in_vec: Vec<Vec<u64>> = s.split(":")
-            .nth(1)
-            .unwrap()
-            .trim()
-            .split("\r\n")
-            .map(|x| x.split_whitespace().map(|y| y.parse::<u64>().unwrap()).collect::<Vec<u64>>())
-            .collect::<Vec<Vec<u64>>>();
-
-        let value = value_in_vec.iter().map(|x| {
-            Location {
-                start_destination: x[0],
-                start_source: x[1],
-                length: x[2],
-            }
-        }).collect::<Vec<Location>>();
+        let (header, lines) = s.split_once(":").ok_or(format!("Map must start with a name and ':': {}", s))?;
+        let name = header.split_whitespace().next().ok_or(format!("Map has no name: {}", s))?;
+        let (from, to) = name.split_once("-to-").ok_or(format!("Map name must be <from>-to-<to>: {}", name))?;
 
-        let from = name.clone().nth(0).unwrap().to_string();
-        let to = name.clone().nth(1).unwrap().to_string();
+        let value = lines.trim()
+            .lines()
+            .map(|x| x.parse::<Location>())
+            .collect::<Result<Vec<Location>, _>>()?;
 
         Ok(Map {
-            from,
-            to,
+            from: from.to_string(),
+            to: to.to_string(),
             value,
         })
     }
@@ -152,39 +252,316 @@ fn generate_seed(seeds: &Seeds) -> Vec<u64> {
     seed_list
 }
 
-pub fn day_5_part_1() {
-    let maps = get_input(true).parse::<Maps>().unwrap();
+/// Every seed of the seed ranges, one at a time, so the seeds are never all held in memory.
+fn seeds_in_ranges(seeds: &Seeds) -> impl Iterator<Item = u64> + '_ {
+    seeds.ranges.iter().flat_map(|(start, length)| *start..*start + *length)
+}
 
-    let mut lowest_seed: u64 = u64::MAX;
+pub fn seed_to_location(seed: u64, maps: &[Map]) -> u64 {
+    maps.iter().fold(seed, transform_card)
+}
 
-    for seed in maps.seeds.value {
-        let mut transformed_value = Some(seed);
+/// Sends whole ranges of values through a map, split where they cross the map's source ranges.
+fn transform_ranges(ranges: Vec<Range<u64>>, map: &Map) -> Vec<Range<u64>> {
+    let mut transformed: Vec<Range<u64>> = Vec::new();
+    let mut untouched = ranges;
 
-        for map in maps.maps.iter() {
-            if let Some(value) = transformed_value {
-                transformed_value = Some(transform_card(value, map));
+    for location in map.value.iter() {
+        let source = location.start_source..location.start_source + location.length;
+        let mut rest: Vec<Range<u64>> = Vec::new();
+        for range in untouched {
+            if let Some(inside) = intersect(&range, &source) {
+                let offset = inside.start - source.start;
+                transformed.push(location.start_destination + offset..location.start_destination + offset + inside.end - inside.start);
             }
+            rest.extend(subtract(&range, &source));
         }
+        untouched = rest;
+    }
 
-        if transformed_value.unwrap() < lowest_seed {
-            lowest_seed = transformed_value.unwrap();
+    // Values outside every source range keep their number
+    transformed.extend(untouched);
+    transformed
+}
+
+/// The lowest location of the seed ranges, found by sending the ranges through the maps instead of every seed.
+pub fn lowest_location_by_ranges(maps: &Maps) -> u64 {
+    let seeds = maps.seeds.ranges.iter()
+        .map(|(start, length)| *start..*start + *length)
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<Range<u64>>>();
+
+    maps.maps.iter()
+        .fold(seeds, transform_ranges)
+        .iter()
+        .map(|x| x.start)
+        .min()
+        .unwrap_or(u64::MAX)
+}
+
+/// The values the map sends to `value`: one for each destination range holding it, and the value itself when no
+/// source range holds it. Source ranges are expected not to overlap.
+fn untransform_card(value: u64, map: &Map) -> Vec<u64> {
+    let mut sources = map.value.iter()
+        .filter(|x| x.start_destination <= value && value - x.start_destination < x.length)
+        .map(|x| x.start_source + value - x.start_destination)
+        .collect::<Vec<u64>>();
+
+    if !map.value.iter().any(|x| x.start_source <= value && value - x.start_source < x.length) {
+        sources.push(value);
+    }
+
+    sources
+}
+
+/// Every seed that ends at the location.
+fn location_to_seeds(location: u64, maps: &[Map]) -> Vec<u64> {
+    maps.iter().rev().fold(vec![location], |values, map| {
+        values.iter().flat_map(|x| untransform_card(*x, map)).collect()
+    })
+}
+
+/// Sends whole ranges of values back through a map, to the ranges of values the map sends into them.
+fn untransform_ranges(ranges: Vec<Range<u64>>, map: &Map) -> Vec<Range<u64>> {
+    let mut sources: Vec<Range<u64>> = Vec::new();
+    for range in ranges.iter() {
+        for location in map.value.iter() {
+            let destination = location.start_destination..location.start_destination + location.length;
+            if let Some(inside) = intersect(range, &destination) {
+                let offset = inside.start - destination.start;
+                sources.push(location.start_source + offset..location.start_source + offset + inside.end - inside.start);
+            }
         }
+
+        // Values outside every source range are their own source
+        let outside = map.value.iter().fold(vec![range.clone()], |rest, location| {
+            let source = location.start_source..location.start_source + location.length;
+            rest.iter().flat_map(|x| subtract(x, &source)).collect()
+        });
+        sources.extend(outside);
     }
 
-    println!("Lowest seed: {}", lowest_seed);
+    sources
+}
+
+/// Every range of seeds that ends in the range of locations.
+fn locations_to_seeds(locations: Range<u64>, maps: &[Map]) -> Vec<Range<u64>> {
+    maps.iter().rev().fold(vec![locations], untransform_ranges)
+}
+
+/// The lowest location of the seed ranges, searched upward from location 0: windows of locations twice as large each
+/// time are sent back to the seeds until one holds a seed of the ranges, then that window is halved down to the
+/// location.
+fn lowest_location_by_search(maps: &Maps) -> u64 {
+    let seeds = maps.seeds.ranges.iter()
+        .map(|(start, length)| *start..*start + *length)
+        .collect::<Vec<Range<u64>>>();
+    let holds_seed = |locations: Range<u64>| {
+        locations_to_seeds(locations, &maps.maps).iter().any(|x| seeds.iter().any(|seed| intersect(x, seed).is_some()))
+    };
+
+    let mut window = 0..1;
+    while !holds_seed(window.clone()) {
+        if window.end == u64::MAX {
+            return u64::MAX;
+        }
+        window = window.end..window.end.saturating_mul(2);
+    }
+
+    while window.end - window.start > 1 {
+        let middle = window.start + (window.end - window.start) / 2;
+        window = match holds_seed(window.start..middle) {
+            true => window.start..middle,
+            false => middle..window.end,
+        };
+    }
+
+    window.start
+}
+
+/// A stretch of values, from `start` up to but excluding `end`, that all move by the same offset.
+#[derive(Debug, Clone, PartialEq)]
+struct Segment {
+    start: u64,
+    end: u64,
+    offset: i64,
+}
+
+/// One or more maps applied one after another, as sorted segments that cover every value. Neighbouring segments
+/// never share their offset.
+#[derive(Debug, Clone, PartialEq)]
+struct PiecewiseMap {
+    from: String,
+    to: String,
+    segments: Vec<Segment>,
 }
 
-pub fn day_5_part_2() { // Take a while to run (about 30 to 60 minutes)
+impl PiecewiseMap {
+    /// The map that leaves every value of the category unchanged.
+    fn identity(category: &str) -> PiecewiseMap {
+        PiecewiseMap {
+            from: category.to_string(),
+            to: category.to_string(),
+            segments: vec![Segment { start: 0, end: u64::MAX, offset: 0 }],
+        }
+    }
+
+    /// The map as segments. Where source ranges overlap, the first one listed wins, as in `transform_card`.
+    fn from_map(map: &Map) -> Result<PiecewiseMap, Box<dyn Error>> {
+        let mut bounds = vec![0, u64::MAX];
+        for location in map.value.iter() {
+            bounds.push(location.start_source);
+            bounds.push(location.start_source.saturating_add(location.length));
+        }
+        bounds.sort();
+        bounds.dedup();
+
+        let segments = bounds.windows(2)
+            .map(|x| {
+                let offset = match map.value.iter().find(|location| location.start_source <= x[0] && x[0] - location.start_source < location.length) {
+                    Some(location) => i64::try_from(location.start_destination as i128 - location.start_source as i128)
+                        .map_err(|_| format!("Offset out of range: {} to {}", location.start_source, location.start_destination))?,
+                    None => 0,
+                };
+                Ok(Segment { start: x[0], end: x[1], offset })
+            })
+            .collect::<Result<Vec<Segment>, Box<dyn Error>>>()?;
+
+        Ok(PiecewiseMap {
+            from: map.from.clone(),
+            to: map.to.clone(),
+            segments: merge_segments(segments),
+        })
+    }
+
+    /// The maps applied one after another, from the first to the last.
+    fn from_maps(maps: &[Map]) -> Result<PiecewiseMap, Box<dyn Error>> {
+        let Some(first) = maps.first() else {
+            return Ok(PiecewiseMap::identity(""));
+        };
+
+        maps[1..].iter().try_fold(PiecewiseMap::from_map(first)?, |composed, map| composed.then(&PiecewiseMap::from_map(map)?))
+    }
+
+    /// This map followed by `next`: every segment is cut where its values land across the segments of `next`.
+    /// Fails when a value would be moved out of `u64`, or when the offsets add up beyond `i64`.
+    fn then(&self, next: &PiecewiseMap) -> Result<PiecewiseMap, Box<dyn Error>> {
+        let mut segments: Vec<Segment> = Vec::new();
+        for segment in self.segments.iter() {
+            let mut start = segment.start;
+            while start < segment.end {
+                let moved = start.checked_add_signed(segment.offset)
+                    .filter(|x| *x < u64::MAX)
+                    .ok_or(format!("Value out of range: {} {:+}", start, segment.offset))?;
+                let next_segment = &next.segments[next.segment_index(moved)];
+                let length = next_segment.end.saturating_sub(moved).min(segment.end - start);
+                if length == 0 {
+                    return Err(format!("Value out of range: {} {:+}", start, segment.offset).into());
+                }
+                let offset = segment.offset.checked_add(next_segment.offset)
+                    .ok_or(format!("Offset out of range: {:+} {:+}", segment.offset, next_segment.offset))?;
+                segments.push(Segment { start, end: start + length, offset });
+                start += length;
+            }
+        }
+
+        Ok(PiecewiseMap {
+            from: self.from.clone(),
+            to: next.to.clone(),
+            segments: merge_segments(segments),
+        })
+    }
+
+    /// The index of the segment holding the value, found by binary search.
+    fn segment_index(&self, value: u64) -> usize {
+        self.segments.partition_point(|x| x.end <= value).min(self.segments.len() - 1)
+    }
+
+    fn get(&self, value: u64) -> u64 {
+        value.saturating_add_signed(self.segments[self.segment_index(value)].offset)
+    }
+
+    /// The lowest value the map gives to any value of the range, if the range is not empty.
+    fn lowest_of_range(&self, range: &Range<u64>) -> Option<u64> {
+        if range.is_empty() {
+            return None;
+        }
+
+        self.segments[self.segment_index(range.start)..]
+            .iter()
+            .take_while(|x| x.start < range.end)
+            .map(|x| x.start.max(range.start).saturating_add_signed(x.offset))
+            .min()
+    }
+}
+
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-to-{} function:", self.from, self.to)?;
+        for segment in self.segments.iter() {
+            match segment.end {
+                u64::MAX => write!(f, "\n{}.. {:+}", segment.start, segment.offset)?,
+                end => write!(f, "\n{}..{} {:+}", segment.start, end, segment.offset)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Joins neighbouring segments that move their values by the same offset.
+fn merge_segments(segments: Vec<Segment>) -> Vec<Segment> {
+    let mut merged: Vec<Segment> = Vec::new();
+    for segment in segments {
+        match merged.last_mut() {
+            Some(last) if last.offset == segment.offset && last.end == segment.start => last.end = segment.end,
+            _ => merged.push(segment),
+        }
+    }
+
+    merged
+}
+
+/// The lowest location of the seed ranges, with the maps composed into a single function first.
+fn lowest_location_by_function(maps: &Maps) -> Result<u64, Box<dyn Error>> {
+    let function = PiecewiseMap::from_maps(&maps.maps)?;
+
+    Ok(maps.seeds.ranges.iter()
+        .filter_map(|(start, length)| function.lowest_of_range(&(*start..*start + *length)))
+        .min()
+        .unwrap_or(u64::MAX))
+}
+
+/// Reads the almanac from any reader. Every map is needed before any seed can be located, so it is read whole.
+fn read_almanac<R: BufRead>(mut reader: R) -> io::Result<Maps> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    input.parse::<Maps>().map_err(|x| invalid_data(format!("Invalid almanac: {}", x)))
+}
+
+pub(crate) fn lowest_location<R: BufRead>(reader: R) -> io::Result<u64> {
+    let maps = read_almanac(reader)?;
+    maps.seeds.value.iter()
+        .map(|x| seed_to_location(*x, &maps.maps))
+        .min()
+        .ok_or_else(|| invalid_data("Almanac has no seeds".to_string()))
+}
+
+pub(crate) fn lowest_location_of_ranges<R: BufRead>(reader: R) -> io::Result<u64> {
+    let maps = read_almanac(reader)?;
+    if maps.seeds.ranges.iter().all(|(_, length)| *length == 0) {
+        return Err(invalid_data("Almanac has no seeds".to_string()));
+    }
+
+    Ok(lowest_location_by_ranges(&maps))
+}
+
+pub fn day_5_part_1() {
     let maps = get_input(false).parse::<Maps>().unwrap();
 
     let mut lowest_seed: u64 = u64::MAX;
-    let all_seeds = generate_seed(&maps.seeds);
-    println!("Seed created: {}", all_seeds.len());
 
-    let mut i = 0;
-    let total = all_seeds.len();
-    for seed in all_seeds {
-        i += 1;
+    for seed in maps.seeds.value {
         let mut transformed_value = Some(seed);
 
         for map in maps.maps.iter() {
@@ -193,24 +570,28 @@ pub fn day_5_part_2() { // Take a while to run (about 30 to 60 minutes)
             }
         }
 
-        if let Some(final_value) = transformed_value {
-            if final_value < lowest_seed {
-                lowest_seed = final_value;
-            }
-        }
-
-        // print every 1000 seeds
-        if i % 1000 == 0 {
-            println!("Seed: {} / {}", i, total);
+        if transformed_value.unwrap() < lowest_seed {
+            lowest_seed = transformed_value.unwrap();
         }
     }
 
     println!("Lowest seed: {}", lowest_seed);
 }
 
+pub fn day_5_part_2() {
+    let maps = get_input(false).parse::<Maps>().unwrap();
+
+    println!("Lowest seed: {}", lowest_location_by_ranges(&maps));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generators::day_5::{generate, Size};
+    use crate::utils::differential::{compare, shrink_lines};
+    use crate::utils::random::Random;
+
+    const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
 
     /*
         seeds: 79 14 55 13
@@ -382,4 +763,233 @@ mod tests {
         assert_eq!(maps.maps[6].value[1].start_source, 93);
         assert_eq!(maps.maps[6].value[1].length, 4);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_path() {
+        let maps = get_input(true).parse::<Maps>().unwrap();
+        let path = maps.path("soil", "humidity").unwrap();
+
+        assert_eq!(
+            path.iter().map(|x| x.to.as_str()).collect::<Vec<&str>>(),
+            vec!["fertilizer", "water", "light", "temperature", "humidity"]
+        );
+        assert!(maps.path("seed", "seed").unwrap().is_empty());
+        assert!(maps.path("soil", "seed").unwrap_err().to_string().contains("no map from location"));
+    }
+
+    #[test]
+    fn test_maps_resolved_by_category() {
+        let input = get_input(true);
+        let mut blocks = split_blocks(input);
+        blocks[1..].reverse();
+        let maps = blocks.join("\n\n").parse::<Maps>().unwrap();
+
+        assert_eq!(maps.maps.iter().map(|x| x.from.as_str()).collect::<Vec<&str>>(), CATEGORIES[..7]);
+        assert_eq!(lowest_location(blocks.join("\n\n").as_bytes()).unwrap(), 35);
+    }
+
+    #[test]
+    fn test_invalid_almanacs() {
+        let input = get_input(true);
+        let blocks = split_blocks(input);
+        let error = |blocks: Vec<&str>| blocks.join("\n\n").parse::<Maps>().unwrap_err().to_string();
+
+        let missing = [&blocks[..3], &blocks[4..]].concat();
+        assert!(error(missing).starts_with("Missing link: no map from fertilizer"));
+
+        let duplicate = [&blocks[..], &blocks[1..2]].concat();
+        assert!(error(duplicate).starts_with("Duplicate maps from seed"));
+
+        let cycle = [&blocks[..], &["location-to-soil map:\n0 0 1"]].concat();
+        assert!(error(cycle).starts_with("Cycle through"));
+
+        let overlapping = [&blocks[..1], &["seed-to-soil map:\n50 98 2\n52 50 49"], &blocks[2..]].concat();
+        assert_eq!(error(overlapping), "Overlapping source ranges in the seed-to-soil map: 50..99 and 98..100");
+    }
+
+    #[test]
+    fn test_malformed_seeds() {
+        assert!("seeds: 79 14 55".parse::<Seeds>().unwrap_err().to_string().starts_with("Seeds must come in pairs"));
+        assert!("seeds: 79 x".parse::<Seeds>().is_err());
+        assert!("79 14".parse::<Seeds>().is_err());
+        assert!(format!("seeds: {} 1", u64::MAX).parse::<Seeds>().is_err());
+    }
+
+    #[test]
+    fn test_malformed_maps() {
+        assert_eq!("seed-to-soil map:\n50 98".parse::<Map>().unwrap_err().to_string(), "Map line must have 3 numbers: 50 98");
+        assert!("seed-to-soil map:\n50 98 x".parse::<Map>().is_err());
+        assert!("seed-to-soil map:\n50 98 2 1".parse::<Map>().is_err());
+        assert!(format!("seed-to-soil map:\n0 {} 2", u64::MAX).parse::<Map>().is_err());
+        assert!("seed-soil map:\n50 98 2".parse::<Map>().unwrap_err().to_string().starts_with("Map name must be"));
+        assert!("seed-to-soil map\n50 98 2".parse::<Map>().is_err());
+        assert!(":\n50 98 2".parse::<Map>().is_err());
+        assert!("seeds: 79 14\n\nseed-to-soil map:\n50 98".parse::<Maps>().is_err());
+    }
+
+    #[test]
+    fn test_lowest_location() {
+        let input = get_input(true);
+
+        assert_eq!(lowest_location(input.as_bytes()).unwrap(), 35);
+        assert_eq!(lowest_location_of_ranges(input.as_bytes()).unwrap(), 46);
+
+        let no_seeds = input.replace("seeds: 79 14 55 13", "seeds:");
+        assert_eq!(lowest_location(no_seeds.as_bytes()).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(lowest_location_of_ranges(no_seeds.as_bytes()).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_seeds_in_ranges() {
+        let maps = get_input(true).parse::<Maps>().unwrap();
+
+        assert_eq!(seeds_in_ranges(&maps.seeds).collect::<Vec<u64>>(), generate_seed(&maps.seeds));
+    }
+
+    #[test]
+    fn test_piecewise_map() {
+        let maps = get_input(true).parse::<Maps>().unwrap();
+        let seed_to_soil = PiecewiseMap::from_map(&maps.maps[0]).unwrap();
+
+        assert_eq!(seed_to_soil.to_string(), "seed-to-soil function:\n0..50 +0\n50..98 +2\n98..100 -48\n100.. +0");
+        assert_eq!([79, 14, 55, 13].map(|x| seed_to_soil.get(x)), [81, 14, 57, 13]);
+
+        let seed_to_location = PiecewiseMap::from_maps(&maps.maps).unwrap();
+        assert_eq!((seed_to_location.from.as_str(), seed_to_location.to.as_str()), ("seed", "location"));
+        assert_eq!([79, 14, 55, 13].map(|x| seed_to_location.get(x)), [82, 43, 86, 35]);
+        assert_eq!(lowest_location_by_function(&maps).unwrap(), 46);
+        assert_eq!(PiecewiseMap::from_maps(&[]).unwrap().get(7), 7);
+    }
+
+    #[test]
+    fn test_piecewise_map_near_u64_max() {
+        let top = u64::MAX - 20;
+        let maps = format!("seeds: {top} 10\n\nseed-to-soil map:\n{} {top} 10\n\nsoil-to-location map:\n{} {} 10\n", top + 10, top, top + 10)
+            .parse::<Maps>()
+            .unwrap();
+        let function = PiecewiseMap::from_maps(&maps.maps).unwrap();
+
+        assert_eq!((top..top + 20).map(|x| function.get(x)).collect::<Vec<u64>>(), (top..top + 20).map(|x| seed_to_location(x, &maps.maps)).collect::<Vec<u64>>());
+        assert_eq!(lowest_location_by_function(&maps).unwrap(), top);
+
+        let far = "seeds: 0 1\n\nseed-to-location map:\n18446744073709551614 0 1\n".parse::<Maps>().unwrap();
+        assert!(PiecewiseMap::from_map(&far.maps[0]).is_err());
+        assert!(lowest_location_by_function(&far).is_err());
+    }
+
+    #[test]
+    fn test_piecewise_map_matches_chain() {
+        let result = compare(
+            0..200,
+            |seed| generate(seed, &Size::default()).input,
+            |x| shrink_lines(x, |line| !line.contains(':') && !line.trim().is_empty()),
+            |x| {
+                let maps = x.parse::<Maps>().unwrap();
+                (0..250).map(|value| seed_to_location(value, &maps.maps)).collect::<Vec<u64>>()
+            },
+            |x| {
+                let function = PiecewiseMap::from_maps(&x.parse::<Maps>().unwrap().maps).unwrap();
+                (0..250).map(|value| function.get(value)).collect::<Vec<u64>>()
+            },
+        );
+
+        if let Err(mismatch) = result {
+            panic!("{}", mismatch);
+        }
+    }
+
+    #[test]
+    fn test_location_to_seeds() {
+        let maps = get_input(true).parse::<Maps>().unwrap();
+
+        assert_eq!(location_to_seeds(35, &maps.maps), vec![13]);
+        assert_eq!(untransform_card(50, &maps.maps[0]), vec![98]);
+        assert_eq!(untransform_card(52, &maps.maps[0]), vec![50]);
+        assert_eq!(untransform_card(10, &maps.maps[0]), vec![10]);
+
+        let map = "seed-to-soil map:\n10 0 5".parse::<Map>().unwrap();
+        assert_eq!(untransform_card(12, &map), vec![2, 12]);
+        assert!(untransform_card(3, &map).is_empty());
+        let values = 3..12;
+        assert_eq!(untransform_ranges(vec![values], &map), vec![0..2, 5..12]);
+
+        assert_eq!(locations_to_seeds(46..47, &maps.maps), vec![82..83]);
+        assert_eq!(lowest_location_by_search(&maps), 46);
+    }
+
+    #[test]
+    fn test_inverse_matches_forward() {
+        for seed in 0..100 {
+            let input = generate(seed, &Size::default()).input;
+            let maps = input.parse::<Maps>().unwrap();
+
+            for value in 0..250 {
+                let location = seed_to_location(value, &maps.maps);
+                assert!(location_to_seeds(location, &maps.maps).contains(&value), "{}\n{} -> {}", input, value, location);
+                for source in location_to_seeds(value, &maps.maps) {
+                    assert_eq!(seed_to_location(source, &maps.maps), value, "{}", input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_matches_ranges() {
+        let result = compare(
+            0..200,
+            |seed| generate(seed, &Size::default()).input,
+            |x| shrink_lines(x, |line| !line.contains(':') && !line.trim().is_empty()),
+            |x| lowest_location_by_ranges(&x.parse::<Maps>().unwrap()),
+            |x| lowest_location_by_search(&x.parse::<Maps>().unwrap()),
+        );
+
+        if let Err(mismatch) = result {
+            panic!("{}", mismatch);
+        }
+    }
+
+    #[test]
+    fn test_function_matches_ranges() {
+        let empty = "seeds: 3 0 10 5\n\nseed-to-location map:\n100 0 50\n".parse::<Maps>().unwrap();
+        assert_eq!(lowest_location_by_function(&empty).unwrap(), 110);
+        assert_eq!(PiecewiseMap::identity("seed").lowest_of_range(&(3..3)), None);
+
+        // Empty seed ranges are added in front of the generated ones
+        let generate = |seed: u64| {
+            let mut random = Random::new(seed);
+            let input = generate(seed, &Size::default()).input;
+            let empty_ranges = (0..random.range(0..3)).map(|_| format!(" {} 0", random.range(0..100))).collect::<String>();
+            input.replacen("seeds:", &format!("seeds:{}", empty_ranges), 1)
+        };
+
+        let result = compare(
+            0..200,
+            generate,
+            |x| shrink_lines(x, |line| !line.contains(':') && !line.trim().is_empty()),
+            |x| lowest_location_by_ranges(&x.parse::<Maps>().unwrap()),
+            |x| lowest_location_by_function(&x.parse::<Maps>().unwrap()).unwrap(),
+        );
+
+        if let Err(mismatch) = result {
+            panic!("{}", mismatch);
+        }
+    }
+
+    #[test]
+    fn test_ranges_match_seed_by_seed() {
+        let result = compare(
+            0..200,
+            |seed| generate(seed, &Size::default()).input,
+            |x| shrink_lines(x, |line| !line.contains(':') && !line.trim().is_empty()),
+            |x| {
+                let maps = x.parse::<Maps>().unwrap();
+                seeds_in_ranges(&maps.seeds).map(|seed| seed_to_location(seed, &maps.maps)).min().unwrap_or(u64::MAX)
+            },
+            |x| lowest_location_by_ranges(&x.parse::<Maps>().unwrap()),
+        );
+
+        if let Err(mismatch) = result {
+            panic!("{}", mismatch);
+        }
+    }
+}
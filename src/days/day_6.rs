@@ -1,6 +1,10 @@
 #![allow(dead_code)]
 
-fn get_input(test_input: bool) -> &'static str {
+use std::io::{self, BufRead};
+
+use crate::utils::parse::{invalid_data, read_records};
+
+pub(crate) fn get_input(test_input: bool) -> &'static str {
     match test_input {
         true => include_str!("../../src/inputs/day_6_test.txt"),
         false => include_str!("../../src/inputs/day_6.txt")
@@ -8,83 +12,69 @@ fn get_input(test_input: bool) -> &'static str {
 }
 
 #[derive(Debug)]
-struct Race {
+pub struct Race {
     time: u64,
     distance: u64,
 }
 
-fn parse_line(input: &str) -> Vec<u64> {
+fn parse_line(input: &str) -> io::Result<Vec<u64>> {
     input
-        .split(':')
-        .nth(1)
-        .unwrap()
-        .trim()
+        .split_once(':')
+        .ok_or_else(|| invalid_data(format!("Line must look like name: numbers: {}", input)))?
+        .1
         .split_whitespace()
-        .map(|x| x.trim())
-        .filter(|x| !x.is_empty())
-        .map(|x| x.parse::<u64>().unwrap())
-        .collect::<Vec<u64>>()
+        .map(|x| x.parse::<u64>().map_err(|_| invalid_data(format!("Invalid number: {}", x))))
+        .collect::<io::Result<Vec<u64>>>()
 }
 
-fn vec_to_num(vec: Vec<u64>) -> u64 {
+fn vec_to_num(vec: Vec<u64>) -> io::Result<u64> {
     let mut num = String::new();
     for digit in vec {
         num.push_str(&digit.to_string());
     }
 
-    num.parse::<u64>().unwrap()
+    num.parse::<u64>().map_err(|_| invalid_data(format!("Invalid number: '{}'", num)))
 }
 
-fn parse_races(input: &str) -> Vec<Race> {
-    let times = parse_line(input
-        .lines()
-        .nth(0)
-        .unwrap());
+/// The times and the distances of the race sheet: its first two lines.
+fn parse_sheet(input: &str) -> io::Result<(Vec<u64>, Vec<u64>)> {
+    let mut lines = input.lines();
+    let mut next_line = |name: &str| lines.next().ok_or_else(|| invalid_data(format!("Missing line of {}", name)));
+
+    let times = parse_line(next_line("times")?)?;
+    let distances = parse_line(next_line("distances")?)?;
+
+    Ok((times, distances))
+}
 
-    let distances = parse_line(input
-        .lines()
-        .nth(1)
-        .unwrap());
+pub fn parse_races(input: &str) -> io::Result<Vec<Race>> {
+    let (times, distances) = parse_sheet(input)?;
 
-    // verif if times and distances have the same length, else return an error
     if times.len() != distances.len() {
-        panic!("Error: times and distances have not the same length");
+        return Err(invalid_data(format!("{} times but {} distances", times.len(), distances.len())));
     }
 
     let mut races: Vec<Race> = Vec::new();
-    let mut index = 0;
-    for _time in times.clone().into_iter() {
+    for (time, distance) in times.into_iter().zip(distances) {
         races.push(Race {
-            time: times[index],
-            distance: distances[index],
+            time,
+            distance,
         });
-        index += 1;
     }
 
-    races
+    Ok(races)
 }
 
-fn parse_races_2(input: &str) -> Race {
-    let times = parse_line(input
-        .lines()
-        .nth(0)
-        .unwrap());
+pub fn parse_races_2(input: &str) -> io::Result<Race> {
+    let (times, distances) = parse_sheet(input)?;
 
-    let distances = parse_line(input
-        .lines()
-        .nth(1)
-        .unwrap());
-
-    println!("{:?}", times);
-    println!("{:?}", distances);
-
-    Race {
-        time: vec_to_num(times),
-        distance: vec_to_num(distances),
-    }
+    Ok(Race {
+        time: vec_to_num(times)?,
+        distance: vec_to_num(distances)?,
+    })
 }
 
-fn get_nb_way_to_win(races: Vec<Race>) -> u64 {
+pub fn get_nb_way_to_win(races: Vec<Race>) -> u64 {
     let mut total_ways_to_win = 1;
 
     for race in races {
@@ -100,7 +90,7 @@ fn get_nb_ways_for_race(race: Race) -> u64 {
     let mut num_ways_to_win = 0;
     for press_time in 0..race_time {
         let move_time = race_time - press_time;
-        let speed = press_time.clone();
+        let speed = press_time;
         let moved_distance = speed * move_time;
         if moved_distance > race_distance {
             num_ways_to_win += 1;
@@ -110,16 +100,52 @@ fn get_nb_ways_for_race(race: Race) -> u64 {
     num_ways_to_win
 }
 
+/// Same as `get_nb_ways_for_race`, without trying every press time. The distance grows with the press time up
+/// to half the race, so a binary search finds the shortest winning press, and the longest one mirrors it.
+pub fn get_nb_ways_for_race_fast(race: &Race) -> u64 {
+    let beats_record = |press_time: u64| press_time * (race.time - press_time) > race.distance;
+
+    let half = race.time / 2;
+    if !beats_record(half) {
+        return 0;
+    }
+
+    let (mut low, mut high) = (0, half);
+    while low < high {
+        let middle = (low + high) / 2;
+        if beats_record(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+
+    race.time - 2 * low + 1
+}
+
+/// Reads the two lines of the race sheet from any reader, ignoring what follows them.
+fn read_race_sheet<R: BufRead>(reader: R) -> io::Result<String> {
+    let lines = read_records(reader).take(2).collect::<io::Result<Vec<String>>>()?;
+    Ok(lines.join("\n"))
+}
+
+pub fn multiply_ways_to_win<R: BufRead>(reader: R) -> io::Result<u64> {
+    Ok(get_nb_way_to_win(parse_races(&read_race_sheet(reader)?)?))
+}
+
+pub fn ways_to_win_single_race<R: BufRead>(reader: R) -> io::Result<u64> {
+    Ok(get_nb_ways_for_race_fast(&parse_races_2(&read_race_sheet(reader)?)?))
+}
 
 pub fn day_6_part_1() {
-    let races = parse_races(get_input(false));
+    let races = parse_races(get_input(false)).unwrap();
     let nb_way_to_win = get_nb_way_to_win(races);
     println!("Number of ways to win: {}", nb_way_to_win);
 }
 
 pub fn day_6_part_2() {
-    let race = parse_races_2(get_input(false));
-    let nb_way_to_win = get_nb_ways_for_race(race);
+    let race = parse_races_2(get_input(false)).unwrap();
+    let nb_way_to_win = get_nb_ways_for_race_fast(&race);
     println!("Number of ways to win: {}", nb_way_to_win);
 }
 
@@ -130,11 +156,13 @@ mod tests {
         Distance:  9  40  200
     */
 
-    use crate::days::day_6::{get_input, parse_races};
+    use crate::days::day_6::*;
+    use crate::utils::differential::{compare, shrink_number};
+    use crate::utils::random::Random;
 
     #[test]
     fn test_parse() {
-        let races = parse_races(get_input(true));
+        let races = parse_races(get_input(true)).unwrap();
 
         assert_eq!(races[0].time, 7);
         assert_eq!(races[0].distance, 9);
@@ -145,4 +173,48 @@ mod tests {
         assert_eq!(races[2].time, 30);
         assert_eq!(races[2].distance, 200);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_read_from_reader() {
+        let input = format!("{}\n\nignored", get_input(true));
+
+        assert_eq!(multiply_ways_to_win(input.as_bytes()).unwrap(), 288);
+        assert_eq!(ways_to_win_single_race(input.as_bytes()).unwrap(), 71503);
+    }
+
+    #[test]
+    fn test_invalid_sheet() {
+        for input in ["", "Time: 7 15", "Time: 7 15\nDistance: 9", "Time 7\nDistance: 9", "Time: 7 x\nDistance: 9 40"] {
+            assert_eq!(multiply_ways_to_win(input.as_bytes()).unwrap_err().kind(), io::ErrorKind::InvalidData, "{}", input);
+        }
+        for input in ["", "Time: 7\nDistance:", "Time: 9999999999 9999999999\nDistance: 9"] {
+            assert_eq!(ways_to_win_single_race(input.as_bytes()).unwrap_err().kind(), io::ErrorKind::InvalidData, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_fast_count_matches_loop() {
+        let generate = |seed: u64| {
+            let mut random = Random::new(seed);
+            let time = random.range(0..200);
+            (time, random.range(0..time * time / 4 + 2))
+        };
+        let shrink = |(time, distance): &(u64, u64)| {
+            let mut smaller = shrink_number(*time).into_iter().map(|x| (x, *distance)).collect::<Vec<_>>();
+            smaller.extend(shrink_number(*distance).into_iter().map(|x| (*time, x)));
+            smaller
+        };
+
+        let result = compare(
+            0..1000,
+            generate,
+            shrink,
+            |(time, distance)| get_nb_ways_for_race(Race { time: *time, distance: *distance }),
+            |(time, distance)| get_nb_ways_for_race_fast(&Race { time: *time, distance: *distance }),
+        );
+
+        if let Err(mismatch) = result {
+            panic!("{}", mismatch);
+        }
+    }
+}
@@ -1,6 +1,48 @@
-pub(crate) mod day_1;
-pub(crate) mod day_2;
-pub(crate) mod day_3;
-pub(crate) mod day_4;
-pub(crate) mod day_5;
-pub(crate) mod day_6;
\ No newline at end of file
+use std::io::{self, BufRead};
+
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+pub mod solutions;
+
+/// Solves a part on an input read from a file or a pipe, and returns the answer.
+pub type StreamedSolver = fn(Box<dyn BufRead>) -> io::Result<u64>;
+
+/// The parts that read their input line by line instead of whole, as (day, part, solver). Each one also has its
+/// entry in `SOLUTIONS`.
+pub const STREAMED_PARTS: &[(u32, u32, StreamedSolver)] = &[
+    (1, 1, day_1::sum_calibration_values),
+    (1, 2, day_1::sum_calibration_values_with_letters),
+    (2, 1, day_2::sum_possible_games),
+    (2, 2, day_2::sum_powers_of_cubes),
+    (4, 1, day_4::total_points),
+    (4, 2, day_4::total_scratchcards),
+    (6, 1, day_6::multiply_ways_to_win),
+    (6, 2, day_6::ways_to_win_single_race),
+];
+
+/// The days with at least one solved part, in order.
+pub fn solved_days() -> Vec<u32> {
+    let mut days = solutions::SOLUTIONS.iter().map(|(day, _, _)| *day).collect::<Vec<u32>>();
+    days.sort();
+    days.dedup();
+
+    days
+}
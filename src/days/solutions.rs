@@ -0,0 +1,193 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use num::BigInt;
+
+use super::*;
+
+/// The answer to a part, with the time spent parsing the input and solving the puzzle.
+/// Solvers that parse their input as they go have no separate parsing time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    pub value: String,
+    pub parse: Option<Duration>,
+    pub solve: Duration,
+}
+
+/// Solves a part on the given input.
+pub type Solver = fn(&str) -> Result<Answer, String>;
+
+/// Times the parsing and the solving apart.
+pub fn timed<T, A: Display>(
+    parse: impl FnOnce() -> Result<T, Box<dyn Error>>,
+    solve: impl FnOnce(T) -> Result<A, String>,
+) -> Result<Answer, String> {
+    let start = Instant::now();
+    let parsed = parse().map_err(|x| x.to_string())?;
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let value = solve(parsed)?;
+
+    Ok(Answer {
+        value: value.to_string(),
+        parse: Some(parse_time),
+        solve: start.elapsed(),
+    })
+}
+
+/// Times a solver that parses its input as it goes.
+pub fn timed_together<A: Display>(solve: impl FnOnce() -> Result<A, String>) -> Result<Answer, String> {
+    let start = Instant::now();
+    let value = solve()?;
+
+    Ok(Answer {
+        value: value.to_string(),
+        parse: None,
+        solve: start.elapsed(),
+    })
+}
+
+/// Every solved part, as (day, part, solver). `aoc new` registers the parts of a new day at the end.
+pub const SOLUTIONS: &[(u32, u32, Solver)] = &[
+    (1, 1, |x| timed_together(|| day_1::sum_calibration_values(x.as_bytes()).map_err(|x| x.to_string()))),
+    (1, 2, |x| timed_together(|| day_1::sum_calibration_values_with_letters(x.as_bytes()).map_err(|x| x.to_string()))),
+    (2, 1, |x| timed_together(|| day_2::sum_possible_games(x.as_bytes()).map_err(|x| x.to_string()))),
+    (2, 2, |x| timed_together(|| day_2::sum_powers_of_cubes(x.as_bytes()).map_err(|x| x.to_string()))),
+    (3, 1, |x| timed_together(|| Ok(day_3::sum_part_numbers(x)))),
+    (3, 2, |x| timed_together(|| Ok(day_3::sum_gear_ratios(x)))),
+    (4, 1, |x| timed_together(|| day_4::total_points(x.as_bytes()).map_err(|x| x.to_string()))),
+    (4, 2, |x| timed_together(|| day_4::total_scratchcards(x.as_bytes()).map_err(|x| x.to_string()))),
+    (5, 1, |x| timed(
+        || x.parse::<day_5::Maps>(),
+        |maps| maps.seeds.value.iter().map(|x| day_5::seed_to_location(*x, &maps.maps)).min().ok_or("No seeds".to_string()),
+    )),
+    (5, 2, |x| timed(
+        || x.parse::<day_5::Maps>(),
+        |maps| match maps.seeds.ranges.iter().all(|(_, length)| *length == 0) {
+            true => Err("No seeds".to_string()),
+            false => Ok(day_5::lowest_location_by_ranges(&maps)),
+        },
+    )),
+    (6, 1, |x| timed(|| Ok(day_6::parse_races(x)?), |races| Ok(day_6::get_nb_way_to_win(races)))),
+    (6, 2, |x| timed(|| Ok(day_6::parse_races_2(x)?), |race| Ok(day_6::get_nb_ways_for_race_fast(&race)))),
+    (12, 1, |x| timed(|| day_12::parse_records(x), |records| Ok(day_12::sum_arrangements(&records)))),
+    (12, 2, |x| timed(
+        || Ok(day_12::parse_records(x)?.iter().map(|x| x.unfold(day_12::UNFOLD_FACTOR)).collect::<Vec<_>>()),
+        |records| Ok(day_12::sum_arrangements(&records)),
+    )),
+    (13, 1, |x| timed(|| day_13::parse_patterns(x), |patterns| day_13::summarize(&patterns, 0).map_err(|x| x.to_string()))),
+    (13, 2, |x| timed(|| day_13::parse_patterns(x), |patterns| day_13::summarize(&patterns, 1).map_err(|x| x.to_string()))),
+    (14, 1, |x| timed(|| x.parse::<day_14::Platform>(), |mut platform| {
+        platform.tilt(day_14::Direction::North);
+        Ok(platform.north_load())
+    })),
+    (14, 2, |x| timed(|| x.parse::<day_14::Platform>(), |platform| Ok(platform.load_after_spins(day_14::SPIN_CYCLES)))),
+    (15, 1, |x| timed(|| Ok(day_15::parse_sequence(x)), |steps| Ok(steps.iter().map(|x| day_15::hash(x)).sum::<usize>()))),
+    (15, 2, |x| timed(
+        || day_15::parse_sequence(x).iter().map(|x| x.parse::<day_15::Operation>()).collect::<Result<Vec<_>, _>>(),
+        |operations| {
+            let mut boxes = day_15::LensBoxes::new();
+            for operation in operations.iter() {
+                boxes.apply(operation);
+            }
+            Ok(boxes.focusing_power())
+        },
+    )),
+    (16, 1, |x| timed(
+        || x.parse::<day_16::Contraption>(),
+        |contraption| Ok(contraption.energized(day_16::Entry { x: 0, y: 0, direction: day_16::Direction::Right })),
+    )),
+    (16, 2, |x| timed(|| x.parse::<day_16::Contraption>(), |contraption| {
+        let threads = thread::available_parallelism().map_or(1, |x| x.get());
+        contraption.best_entry(threads).map(|(_, energized)| energized).ok_or("No entry".to_string())
+    })),
+    (17, 1, |x| timed(
+        || x.parse::<day_17::City>(),
+        |city| city.least_heat_loss(day_17::NORMAL_CRUCIBLE).ok_or("No path to the factory".to_string()),
+    )),
+    (17, 2, |x| timed(
+        || x.parse::<day_17::City>(),
+        |city| city.least_heat_loss(day_17::ULTRA_CRUCIBLE).ok_or("No path to the factory".to_string()),
+    )),
+    (18, 1, |x| timed(
+        || Ok(day_18::parse_steps(x)?.iter().map(|x| x.instruction).collect::<Vec<_>>()),
+        |instructions| Ok(day_18::lagoon_size(&instructions)),
+    )),
+    (18, 2, |x| timed(
+        || Ok(day_18::parse_steps(x)?.iter().map(|x| x.decode_color()).collect::<Vec<_>>()),
+        |instructions| Ok(day_18::lagoon_size(&instructions)),
+    )),
+    (19, 1, |x| timed(|| x.parse::<day_19::System>(), |system| Ok(system.sum_accepted_ratings()))),
+    (19, 2, |x| timed(|| x.parse::<day_19::System>(), |system| Ok(system.count_all_accepted()))),
+    (20, 1, |x| timed(|| x.parse::<day_20::Circuit>(), |mut circuit| Ok(circuit.pulse_product(day_20::NB_PRESSES)))),
+    (20, 2, |x| timed(
+        || x.parse::<day_20::Circuit>(),
+        |mut circuit| circuit.presses_until_low(day_20::TARGET).ok_or(format!(
+            "The target is not fed by a single conjunction whose feeders all fire within {} presses",
+            day_20::MAX_PRESSES,
+        )),
+    )),
+    (21, 1, |x| timed(|| x.parse::<day_21::Garden>(), |garden| Ok(garden.reachable(day_21::STEPS)))),
+    (21, 2, |x| timed(
+        || x.parse::<day_21::Garden>(),
+        |garden| garden.reachable_extrapolated(day_21::INFINITE_STEPS).map_err(|x| x.to_string()),
+    )),
+    (22, 1, |x| timed(|| Ok(day_22::Stack::settle(day_22::parse_bricks(x)?)), |stack| Ok(stack.count_removable()))),
+    (22, 2, |x| timed(|| Ok(day_22::Stack::settle(day_22::parse_bricks(x)?)), |stack| Ok(stack.sum_chain_reactions()))),
+    (23, 1, |x| timed(|| x.parse::<day_23::Trails>(), |trails| trails.longest_hike(true).ok_or("No hike to the end".to_string()))),
+    (23, 2, |x| timed(|| x.parse::<day_23::Trails>(), |trails| trails.longest_hike(false).ok_or("No hike to the end".to_string()))),
+    (24, 1, |x| timed(
+        || day_24::parse_hailstones(x),
+        |hailstones| Ok(day_24::count_intersections(&hailstones, day_24::TEST_AREA)),
+    )),
+    (24, 2, |x| timed(|| day_24::parse_hailstones(x), |hailstones| {
+        let position = day_24::rock_position(&hailstones).ok_or("No rock hits every hailstone".to_string())?;
+        Ok(position.iter().sum::<BigInt>())
+    })),
+    (25, 1, |x| timed(|| x.parse::<day_25::Wiring>(), |wiring| {
+        match wiring.split_groups() {
+            Some((wires, product)) if wires == day_25::WIRES_TO_CUT => Ok(product),
+            Some((wires, _)) => Err(format!("Expected to cut {} wires, but the minimum cut is {}", day_25::WIRES_TO_CUT, wires)),
+            None => Err("No wires to cut".to_string()),
+        }
+    })),
+];
+
+/// The answer of one part on the given input.
+pub fn solve(day: u32, part: u32, input: &str) -> Result<Answer, String> {
+    let (_, _, solver) = SOLUTIONS
+        .iter()
+        .find(|(d, p, _)| *d == day && *p == part)
+        .ok_or(format!("Day {} part {} does not return its answer", day, part))?;
+
+    solver(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_streamed_part_is_solved() {
+        for (day, part, _) in STREAMED_PARTS {
+            assert!(SOLUTIONS.iter().any(|(d, p, _)| d == day && p == part), "Day {} part {} is missing", day, part);
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let answer = solve(13, 1, day_13::get_input(true)).unwrap();
+
+        assert_eq!(answer.value, "405");
+        assert!(answer.parse.is_some());
+        assert_eq!(solve(6, 2, day_6::get_input(true)).unwrap().value, "71503");
+        assert_eq!(solve(1, 1, include_str!("../../src/inputs/day_1_test.txt")).unwrap().value, "142");
+        assert!(solve(1, 1, "").unwrap().parse.is_none());
+        assert!(solve(17, 1, "x").is_err());
+        assert!(solve(12, 1, "???.### 1,x").is_err());
+        assert!(solve(26, 1, "").is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::days::day_1::LETTERS_AS_DIGITS;
+use crate::utils::random::Random;
+
+use super::Generated;
+
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub lines: usize,
+    pub max_tokens: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            lines: 50,
+            max_tokens: 12,
+        }
+    }
+}
+
+/// Calibration lines mixing letters, digits and spelled digits, with at least one digit on each line.
+pub fn generate(seed: u64, size: &Size) -> Generated {
+    let mut random = Random::new(seed);
+    let mut lines: Vec<String> = Vec::new();
+
+    for _ in 0..size.lines {
+        let mut tokens: Vec<String> = Vec::new();
+        for _ in 0..random.range(0..size.max_tokens as u64 + 1) {
+            let token = match random.range(0..4) {
+                0 => random.range(1..10).to_string(),
+                1 => random.pick(&LETTERS_AS_DIGITS).0.to_string(),
+                _ => ((b'a' + random.range(0..26) as u8) as char).to_string(),
+            };
+            tokens.push(token);
+        }
+
+        let position = random.range(0..tokens.len() as u64 + 1) as usize;
+        tokens.insert(position, random.range(1..10).to_string());
+        lines.push(tokens.concat());
+    }
+
+    let input = lines.join("\n");
+    let answers = oracle(&input).map(|x| x.expect("Error: generated lines always have a digit"));
+
+    Generated {
+        input,
+        answers,
+    }
+}
+
+/// Looks for a digit, or a spelled digit when `letters` is set, at every character of the line.
+/// Blank lines are skipped, and a line without any digit is an error, as for the solver.
+pub fn oracle(input: &str) -> [Result<u64, String>; 2] {
+    let calibration_value = |line: &str, letters: bool| -> Result<u64, String> {
+        let digits = line.char_indices()
+            .filter_map(|(i, character)| {
+                let spelled = LETTERS_AS_DIGITS.iter()
+                    .find(|(word, _)| letters && line[i..].starts_with(word))
+                    .map(|(_, digit)| *digit);
+                character.to_digit(10).or(spelled)
+            })
+            .collect::<Vec<u32>>();
+
+        match (digits.first(), digits.last()) {
+            (Some(first), Some(last)) => Ok((first * 10 + last) as u64),
+            _ => Err(format!("Line has no digit: {}", line)),
+        }
+    };
+
+    [false, true].map(|letters| {
+        input.lines()
+            .filter(|x| !x.trim().is_empty())
+            .map(|x| calibration_value(x, letters))
+            .sum()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_1::{sum_calibration_values, sum_calibration_values_with_letters};
+
+    #[test]
+    fn test_oracle() {
+        assert_eq!(oracle(include_str!("../../src/inputs/day_1_test.txt")), [Ok(142), Ok(142)]);
+        assert_eq!(oracle(include_str!("../../src/inputs/day_1_test_2.txt"))[1], Ok(281));
+    }
+
+    #[test]
+    fn test_oracle_matches_solver() {
+        for input in ["é1ü2\n\nñine7", "a1b\nxyz", "two\n3", "ñ"] {
+            let [first, second] = oracle(input);
+
+            assert_eq!(first.ok(), sum_calibration_values(input.as_bytes()).ok(), "{}", input);
+            assert_eq!(second.ok(), sum_calibration_values_with_letters(input.as_bytes()).ok(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        let size = Size::default();
+        let generated = generate(1, &size);
+
+        assert_eq!(generated, generate(1, &size));
+        assert_eq!(generated.input.lines().count(), size.lines);
+        assert_eq!(sum_calibration_values(generated.input.as_bytes()).unwrap(), generated.answers[0]);
+        assert_eq!(sum_calibration_values_with_letters(generated.input.as_bytes()).unwrap(), generated.answers[1]);
+    }
+}
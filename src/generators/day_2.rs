@@ -0,0 +1,99 @@
+use crate::days::day_2::{MAX_BLUE, MAX_GREEN, MAX_RED};
+use crate::utils::random::Random;
+
+use super::Generated;
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub games: usize,
+    pub max_draws: usize,
+    pub max_cubes: u64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            games: 30,
+            max_draws: 5,
+            max_cubes: 20,
+        }
+    }
+}
+
+/// Games of one to `max_draws` draws, each showing at least one color.
+pub fn generate(seed: u64, size: &Size) -> Generated {
+    let mut random = Random::new(seed);
+    let mut games: Vec<String> = Vec::new();
+
+    for id in 1..=size.games {
+        let mut draws: Vec<String> = Vec::new();
+        for _ in 0..random.range(1..size.max_draws as u64 + 1) {
+            let mut colors = COLORS.to_vec();
+            random.shuffle(&mut colors);
+            colors.truncate(random.range(1..4) as usize);
+
+            let cubes = colors.iter()
+                .map(|color| format!("{} {}", random.range(1..size.max_cubes + 1), color))
+                .collect::<Vec<String>>();
+            draws.push(cubes.join(", "));
+        }
+        games.push(format!("Game {}: {}", id, draws.join("; ")));
+    }
+
+    let input = games.join("\n");
+    let answers = oracle(&input);
+
+    Generated {
+        input,
+        answers,
+    }
+}
+
+/// Finds the most cubes of each color shown in a game, which decides both parts.
+pub fn oracle(input: &str) -> [u64; 2] {
+    let limits = [MAX_RED as u64, MAX_GREEN as u64, MAX_BLUE as u64];
+    let mut answers = [0, 0];
+
+    for line in input.lines() {
+        let (game, draws) = line.split_once(':').unwrap();
+        let id = game.trim_start_matches("Game").trim().parse::<u64>().unwrap();
+
+        let mut most = [0; 3];
+        for cubes in draws.split([';', ',']) {
+            let (count, color) = cubes.trim().split_once(' ').unwrap();
+            let index = COLORS.iter().position(|x| *x == color).unwrap();
+            most[index] = most[index].max(count.parse::<u64>().unwrap());
+        }
+
+        if most.iter().zip(limits).all(|(count, limit)| *count <= limit) {
+            answers[0] += id;
+        }
+        answers[1] += most.iter().product::<u64>();
+    }
+
+    answers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_2::{sum_possible_games, sum_powers_of_cubes};
+
+    #[test]
+    fn test_oracle() {
+        assert_eq!(oracle(include_str!("../../src/inputs/day_2_test.txt")), [8, 2286]);
+    }
+
+    #[test]
+    fn test_generate() {
+        let size = Size::default();
+        let generated = generate(1, &size);
+
+        assert_eq!(generated, generate(1, &size));
+        assert_eq!(generated.input.lines().count(), size.games);
+        assert_eq!(sum_possible_games(generated.input.as_bytes()).unwrap(), generated.answers[0]);
+        assert_eq!(sum_powers_of_cubes(generated.input.as_bytes()).unwrap(), generated.answers[1]);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::utils::random::Random;
+
+use super::Generated;
+
+const SYMBOLS: [char; 9] = ['#', '+', '$', '/', '@', '=', '%', '&', '-'];
+
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            width: 20,
+            height: 12,
+        }
+    }
+}
+
+/// An engine schematic scattered with numbers of up to three digits, symbols and gears.
+pub fn generate(seed: u64, size: &Size) -> Generated {
+    let mut random = Random::new(seed);
+    let mut rows: Vec<String> = Vec::new();
+
+    for _ in 0..size.height {
+        let mut row = String::new();
+        while row.len() < size.width {
+            let number = random.range(1..1000).to_string();
+            match random.range(0..8) {
+                // Numbers are followed by a dot, so two numbers never touch
+                0 | 1 if row.len() + number.len() < size.width => row.push_str(&format!("{}.", number)),
+                2 => row.push('*'),
+                3 => row.push(*random.pick(&SYMBOLS)),
+                _ => row.push('.'),
+            }
+        }
+        rows.push(row);
+    }
+
+    let input = rows.join("\n");
+    let answers = oracle(&input);
+
+    Generated {
+        input,
+        answers,
+    }
+}
+
+/// Looks at every cell around each number, and at the numbers around each `*`.
+pub fn oracle(input: &str) -> [u64; 2] {
+    let grid = input.lines().map(|x| x.chars().collect::<Vec<char>>()).collect::<Vec<Vec<char>>>();
+
+    // Every number as (row, first column, last column, value)
+    let mut numbers: Vec<(usize, usize, usize, u64)> = Vec::new();
+    for (y, row) in grid.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            if row[x].is_ascii_digit() {
+                let start = x;
+                while x < row.len() && row[x].is_ascii_digit() {
+                    x += 1;
+                }
+                let value = row[start..x].iter().collect::<String>().parse::<u64>().unwrap();
+                numbers.push((y, start, x - 1, value));
+            } else {
+                x += 1;
+            }
+        }
+    }
+
+    let touches = |(y, start, end, _): &(usize, usize, usize, u64), cell_y: usize, cell_x: usize| {
+        y.abs_diff(cell_y) <= 1 && cell_x + 1 >= *start && cell_x <= end + 1
+    };
+
+    let mut answers = [0, 0];
+    for (y, row) in grid.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            if cell.is_ascii_digit() || *cell == '.' {
+                continue;
+            }
+
+            let around = numbers.iter().filter(|number| touches(number, y, x)).collect::<Vec<_>>();
+            if *cell == '*' && around.len() == 2 {
+                answers[1] += around[0].3 * around[1].3;
+            }
+        }
+    }
+
+    answers[0] = numbers.iter()
+        .filter(|number| {
+            grid.iter().enumerate().any(|(y, row)| {
+                row.iter().enumerate().any(|(x, cell)| !cell.is_ascii_digit() && *cell != '.' && touches(number, y, x))
+            })
+        })
+        .map(|(_, _, _, value)| value)
+        .sum();
+
+    answers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_3::{sum_gear_ratios, sum_part_numbers};
+
+    #[test]
+    fn test_oracle() {
+        assert_eq!(oracle(include_str!("../../src/inputs/day_3_test.txt")), [4361, 467835]);
+    }
+
+    #[test]
+    fn test_generate() {
+        let size = Size::default();
+        let generated = generate(1, &size);
+
+        assert_eq!(generated, generate(1, &size));
+        assert!(generated.input.lines().all(|x| x.len() == size.width));
+        assert_eq!(generated.input.lines().count(), size.height);
+        assert_eq!(sum_part_numbers(&generated.input) as u64, generated.answers[0]);
+        assert_eq!(sum_gear_ratios(&generated.input) as u64, generated.answers[1]);
+    }
+
+    #[test]
+    fn test_solver_matches_oracle() {
+        for seed in 0..300 {
+            let generated = generate(seed, &Size::default());
+            let answers = [sum_part_numbers(&generated.input) as u64, sum_gear_ratios(&generated.input) as u64];
+
+            assert_eq!(answers, generated.answers, "seed {}:\n{}", seed, generated.input);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::utils::random::Random;
+
+use super::Generated;
+
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub cards: usize,
+    pub winning: usize,
+    pub hand: usize,
+    pub max_number: u64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            cards: 20,
+            winning: 5,
+            hand: 8,
+            max_number: 100,
+        }
+    }
+}
+
+/// Scratchcards with distinct numbers on each side, which never win copies past the last card.
+pub fn generate(seed: u64, size: &Size) -> Generated {
+    let mut random = Random::new(seed);
+    let width = (size.max_number - 1).to_string().len();
+    let format = |numbers: &[u64]| {
+        numbers.iter().map(|x| format!("{:>width$}", x)).collect::<Vec<String>>().join(" ")
+    };
+
+    let mut cards: Vec<String> = Vec::new();
+    for id in 1..=size.cards {
+        let mut numbers = random.distinct(1..size.max_number, size.winning + size.hand);
+        let remaining = size.cards - id;
+        let matches = random.range(0..size.winning.min(size.hand).min(remaining) as u64 + 1) as usize;
+
+        // The first `matches` winning numbers are also in the hand
+        let winning = numbers.drain(..size.winning).collect::<Vec<u64>>();
+        let mut hand = winning[..matches].to_vec();
+        hand.extend(numbers.drain(..size.hand - matches));
+        random.shuffle(&mut hand);
+
+        cards.push(format!("Card {:>3}: {} | {}", id, format(&winning), format(&hand)));
+    }
+
+    let input = cards.join("\n");
+    let answers = oracle(&input);
+
+    Generated {
+        input,
+        answers,
+    }
+}
+
+/// Counts the matches of every card, then scratches every copy of every card one by one.
+pub fn oracle(input: &str) -> [u64; 2] {
+    let matches = input.lines()
+        .map(|line| {
+            let (winning, hand) = line.split_once(':').unwrap().1.split_once('|').unwrap();
+            let winning = winning.split_whitespace().collect::<Vec<&str>>();
+            hand.split_whitespace().filter(|x| winning.contains(x)).count()
+        })
+        .collect::<Vec<usize>>();
+
+    let points = matches.iter()
+        .filter(|x| **x > 0)
+        .map(|x| 1 << (x - 1))
+        .sum::<u64>();
+
+    let mut copies: Vec<u64> = vec![1; matches.len()];
+    for card in 0..matches.len() {
+        for _ in 0..copies[card] {
+            for next_card in copies.iter_mut().skip(card + 1).take(matches[card]) {
+                *next_card += 1;
+            }
+        }
+    }
+
+    [points, copies.iter().sum()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_4::{total_points, total_scratchcards};
+
+    #[test]
+    fn test_oracle() {
+        assert_eq!(oracle(include_str!("../../src/inputs/day_4_test.txt")), [13, 30]);
+    }
+
+    #[test]
+    fn test_generate() {
+        let size = Size::default();
+        let generated = generate(1, &size);
+
+        assert_eq!(generated, generate(1, &size));
+        assert_eq!(generated.input.lines().count(), size.cards);
+        assert_eq!(total_points(generated.input.as_bytes()).unwrap(), generated.answers[0]);
+        assert_eq!(total_scratchcards(generated.input.as_bytes()).unwrap(), generated.answers[1]);
+    }
+}
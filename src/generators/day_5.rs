@@ -0,0 +1,112 @@
+use crate::utils::random::Random;
+
+use super::Generated;
+
+const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub seed_ranges: usize,
+    pub max_seed_range: u64,
+    pub ranges_per_map: usize,
+    pub max_value: u64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            seed_ranges: 3,
+            max_seed_range: 30,
+            ranges_per_map: 3,
+            max_value: 200,
+        }
+    }
+}
+
+/// An almanac from seeds to locations, with source ranges that never overlap within a map.
+pub fn generate(seed: u64, size: &Size) -> Generated {
+    let mut random = Random::new(seed);
+
+    let seeds = (0..size.seed_ranges)
+        .map(|_| format!("{} {}", random.range(0..size.max_value), random.range(1..size.max_seed_range + 1)))
+        .collect::<Vec<String>>();
+    let mut blocks = vec![format!("seeds: {}", seeds.join(" "))];
+
+    for categories in CATEGORIES.windows(2) {
+        // Consecutive pairs of distinct bounds give disjoint source ranges
+        let mut bounds = random.distinct(0..size.max_value + 1, size.ranges_per_map * 2);
+        bounds.sort();
+
+        let mut lines = vec![format!("{}-to-{} map:", categories[0], categories[1])];
+        for range in bounds.chunks(2) {
+            lines.push(format!("{} {} {}", random.range(0..size.max_value), range[0], range[1] - range[0]));
+        }
+        random.shuffle(&mut lines[1..]);
+        blocks.push(lines.join("\n"));
+    }
+
+    let input = blocks.join("\n\n");
+    let answers = oracle(&input);
+
+    Generated {
+        input,
+        answers,
+    }
+}
+
+/// Follows every seed through every map, one seed at a time.
+pub fn oracle(input: &str) -> [u64; 2] {
+    let mut blocks = input.split("\n\n");
+    let seeds = blocks.next().unwrap()
+        .trim_start_matches("seeds:")
+        .split_whitespace()
+        .map(|x| x.parse::<u64>().unwrap())
+        .collect::<Vec<u64>>();
+
+    let maps = blocks
+        .map(|block| {
+            block.lines()
+                .skip(1)
+                .map(|line| line.split_whitespace().map(|x| x.parse::<u64>().unwrap()).collect::<Vec<u64>>())
+                .collect::<Vec<Vec<u64>>>()
+        })
+        .collect::<Vec<Vec<Vec<u64>>>>();
+
+    let locate = |seed: u64| {
+        maps.iter().fold(seed, |value, map| {
+            map.iter()
+                .find(|range| range[1] <= value && value < range[1] + range[2])
+                .map_or(value, |range| range[0] + value - range[1])
+        })
+    };
+
+    let lowest = seeds.iter().map(|x| locate(*x)).min().unwrap_or(u64::MAX);
+    let lowest_of_ranges = seeds.chunks(2)
+        .flat_map(|range| range[0]..range[0] + range[1])
+        .map(locate)
+        .min()
+        .unwrap_or(u64::MAX);
+
+    [lowest, lowest_of_ranges]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_5::{lowest_location, lowest_location_of_ranges};
+
+    #[test]
+    fn test_oracle() {
+        assert_eq!(oracle(include_str!("../../src/inputs/day_5_test.txt")), [35, 46]);
+    }
+
+    #[test]
+    fn test_generate() {
+        let size = Size::default();
+        let generated = generate(1, &size);
+
+        assert_eq!(generated, generate(1, &size));
+        assert_eq!(lowest_location(generated.input.as_bytes()).unwrap(), generated.answers[0]);
+        assert_eq!(lowest_location_of_ranges(generated.input.as_bytes()).unwrap(), generated.answers[1]);
+    }
+}
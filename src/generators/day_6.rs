@@ -0,0 +1,81 @@
+use crate::utils::random::Random;
+
+use super::Generated;
+
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub races: usize,
+    pub max_time: u64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            races: 3,
+            max_time: 60,
+        }
+    }
+}
+
+/// A race sheet where every record can be beaten.
+pub fn generate(seed: u64, size: &Size) -> Generated {
+    let mut random = Random::new(seed);
+
+    let times = (0..size.races).map(|_| random.range(2..size.max_time + 1)).collect::<Vec<u64>>();
+    let distances = times.iter()
+        .map(|time| random.range(0..(time / 2) * (time - time / 2)))
+        .collect::<Vec<u64>>();
+
+    let width = distances.iter().chain(times.iter()).map(|x| x.to_string().len()).max().unwrap_or(1);
+    let format = |numbers: &[u64]| {
+        numbers.iter().map(|x| format!("{:>width$}", x)).collect::<Vec<String>>().join("  ")
+    };
+
+    let input = format!("Time:      {}\nDistance:  {}", format(&times), format(&distances));
+    let answers = oracle(&input);
+
+    Generated {
+        input,
+        answers,
+    }
+}
+
+/// Tries every press time of every race.
+pub fn oracle(input: &str) -> [u64; 2] {
+    let ways = |time: u64, distance: u64| (0..=time).filter(|press| press * (time - press) > distance).count() as u64;
+
+    let numbers = input.lines()
+        .map(|line| line.split_once(':').unwrap().1.split_whitespace().collect::<Vec<&str>>())
+        .collect::<Vec<Vec<&str>>>();
+    let (times, distances) = (&numbers[0], &numbers[1]);
+
+    let product = times.iter()
+        .zip(distances.iter())
+        .map(|(time, distance)| ways(time.parse().unwrap(), distance.parse().unwrap()))
+        .product();
+    let single_race = ways(times.concat().parse().unwrap(), distances.concat().parse().unwrap());
+
+    [product, single_race]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_6::{multiply_ways_to_win, ways_to_win_single_race};
+
+    #[test]
+    fn test_oracle() {
+        assert_eq!(oracle(include_str!("../../src/inputs/day_6_test.txt")), [288, 71503]);
+    }
+
+    #[test]
+    fn test_generate() {
+        let size = Size::default();
+        let generated = generate(1, &size);
+
+        assert_eq!(generated, generate(1, &size));
+        assert!(generated.answers[0] > 0);
+        assert_eq!(multiply_ways_to_win(generated.input.as_bytes()).unwrap(), generated.answers[0]);
+        assert_eq!(ways_to_win_single_race(generated.input.as_bytes()).unwrap(), generated.answers[1]);
+    }
+}
@@ -0,0 +1,17 @@
+//! Seeded random inputs for the days, each with the answers given by a brute-force oracle.
+//!
+//! The same seed and size always give the same input, so a failing input can be replayed from its seed.
+
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+
+/// A generated input, with the answers to both parts found by the day's oracle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    pub answers: [u64; 2],
+}
@@ -0,0 +1,231 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::report::Report;
+
+/// The results log, kept in the directory `aoc` runs from.
+pub const RESULTS_LOG: &str = "results.log";
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// One answer found by a run, as a tab-separated line of the results log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub timestamp: u64,
+    pub day: u32,
+    pub part: u32,
+    pub checksum: String,
+    pub answer: String,
+    pub duration: Duration,
+    pub commit: String,
+}
+
+impl std::str::FromStr for Entry {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = s.split('\t').collect::<Vec<&str>>();
+        let [timestamp, day, part, checksum, answer, duration, commit] = fields.as_slice() else {
+            return Err(format!("Log entry must have 7 tab-separated fields: {}", s).into());
+        };
+
+        Ok(Entry {
+            timestamp: timestamp.parse()?,
+            day: day.parse()?,
+            part: part.parse()?,
+            checksum: checksum.to_string(),
+            answer: answer.to_string(),
+            duration: Duration::from_micros(duration.parse()?),
+            commit: commit.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp, self.day, self.part, self.checksum, self.answer, self.duration.as_micros(), self.commit
+        )
+    }
+}
+
+impl Entry {
+    /// The entry of a report that found its answer, if it did.
+    pub fn from_report(report: &Report, timestamp: u64, commit: &str) -> Option<Entry> {
+        let answer = report.result.as_ref().ok()?;
+
+        Some(Entry {
+            timestamp,
+            day: report.day,
+            part: report.part,
+            checksum: report.checksum.clone()?,
+            answer: answer.value.clone(),
+            duration: answer.parse.unwrap_or_default() + answer.solve,
+            commit: commit.to_string(),
+        })
+    }
+}
+
+/// Appends the entries at the end of the log, creating it if needed.
+pub fn append(path: &Path, entries: &[Entry]) -> io::Result<()> {
+    let mut log = OpenOptions::new().create(true).append(true).open(path)?;
+    for entry in entries {
+        writeln!(log, "{}", entry)?;
+    }
+
+    Ok(())
+}
+
+/// Every entry of the log, oldest first. A missing log has no entries.
+pub fn read(path: &Path) -> Result<Vec<Entry>, Box<dyn Error>> {
+    let log = match fs::read_to_string(path) {
+        Ok(log) => log,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error.into()),
+    };
+
+    log.lines()
+        .filter(|x| !x.trim().is_empty())
+        .map(|x| x.parse::<Entry>())
+        .collect()
+}
+
+/// The entries of one part of a day, or of every part, one line each. An answer that differs from the previous
+/// answer for the same part and the same input is flagged.
+pub fn history(entries: &[Entry], day: u32, part: Option<u32>) -> Vec<String> {
+    let entries = entries.iter()
+        .filter(|x| x.day == day && part.is_none_or(|part| part == x.part))
+        .collect::<Vec<&Entry>>();
+
+    let mut lines: Vec<String> = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let previous = entries[..index].iter()
+            .rev()
+            .find(|x| x.part == entry.part && x.checksum == entry.checksum);
+
+        let mut line = format!(
+            "{}  part {}  {}  {}  {:>20}  {:>10.3} ms",
+            format_timestamp(entry.timestamp),
+            entry.part,
+            entry.commit,
+            entry.checksum,
+            entry.answer,
+            entry.duration.as_secs_f64() * 1000.0,
+        );
+        if let Some(previous) = previous.filter(|x| x.answer != entry.answer) {
+            line.push_str(&format!("  CHANGED (was {})", previous.answer));
+        }
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// The current time, in seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |x| x.as_secs())
+}
+
+/// The short hash of the commit checked out, or `unknown` outside of a git repository.
+pub fn current_commit() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|x| x.status.success())
+        .and_then(|x| String::from_utf8(x.stdout).ok())
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+        .unwrap_or("unknown".to_string())
+}
+
+/// The timestamp as a UTC date and time, like `2023-12-25 06:00:00`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let (days, seconds) = (timestamp / SECONDS_PER_DAY, timestamp % SECONDS_PER_DAY);
+
+    // Civil date from the number of days since 1970-01-01, over 400-year eras of 146097 days
+    let days = days as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", year, month, day, seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: u64, part: u32, checksum: &str, answer: &str) -> Entry {
+        Entry {
+            timestamp,
+            day: 5,
+            part,
+            checksum: checksum.to_string(),
+            answer: answer.to_string(),
+            duration: Duration::from_micros(1500),
+            commit: "abc1234".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_entry_round_trip() {
+        let entry = entry(1703484000, 1, "00ff", "35");
+
+        assert_eq!(entry.to_string(), "1703484000\t5\t1\t00ff\t35\t1500\tabc1234");
+        assert_eq!(entry.to_string().parse::<Entry>().unwrap(), entry);
+        assert!("1703484000\t5\t1".parse::<Entry>().is_err());
+    }
+
+    #[test]
+    fn test_history_flags_changed_answer() {
+        let entries = vec![
+            entry(1, 1, "00ff", "35"),
+            entry(2, 2, "00ff", "46"),
+            entry(3, 1, "0aaa", "36"),
+            entry(4, 1, "00ff", "37"),
+            entry(5, 1, "00ff", "37"),
+        ];
+        let lines = history(&entries, 5, Some(1));
+
+        assert_eq!(lines.len(), 4);
+        assert!(!lines[0].contains("CHANGED"));
+        assert!(!lines[1].contains("CHANGED"));
+        assert!(lines[2].ends_with("CHANGED (was 35)"));
+        assert!(!lines[3].contains("CHANGED"));
+        assert_eq!(history(&entries, 5, None).len(), 5);
+        assert!(history(&entries, 6, None).is_empty());
+    }
+
+    #[test]
+    fn test_append_and_read() {
+        let path = std::env::temp_dir().join(format!("aoc_results_{}.log", std::process::id()));
+        let entries = vec![entry(1, 1, "00ff", "35"), entry(2, 2, "00ff", "46")];
+
+        assert!(read(&path).unwrap().is_empty());
+        append(&path, &entries[..1]).unwrap();
+        append(&path, &entries[1..]).unwrap();
+        assert_eq!(read(&path).unwrap(), entries);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(1703484000), "2023-12-25 06:00:00");
+        assert_eq!(format_timestamp(951782400), "2000-02-29 00:00:00");
+    }
+}
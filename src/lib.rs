@@ -0,0 +1,10 @@
+//! Solutions to Advent of Code 2023, with their parsers and the utilities they share.
+//!
+//! The `aoc` binary is a thin command line front end over this library.
+
+pub mod days;
+pub mod generators;
+pub mod history;
+pub mod report;
+pub mod scaffold;
+pub mod utils;
@@ -1,5 +1,142 @@
-mod days;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::process::ExitCode;
 
-fn main() {
-    days::day_6::day_6_part_2();
-}
\ No newline at end of file
+use advent_of_code2023::history::{self, Entry, RESULTS_LOG};
+use advent_of_code2023::{days, report, scaffold};
+
+const USAGE: &str = "Usage:
+    aoc run --day N [--part P]    Run a day, or one of its parts
+        [--input PATH]            Solve the input at PATH instead, or the standard input for -
+        [--format text|json]      Print the answers as text, or as one JSON object per part
+        [--log]                   Append the answers to the results log
+    aoc run --all                 Run every solved day
+        [--format text|json] [--log]
+    aoc history --day N           Show how the logged answers and runtimes of a day changed
+        [--part P]
+    aoc render --day 3            Draw the engine schematic, in colors on a terminal
+        [--input PATH]
+    aoc new --day N               Create the module and the inputs of a new day";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            eprintln!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the command, and fails when a part gives no answer.
+fn run(args: &[String]) -> Result<ExitCode, Box<dyn Error>> {
+    match args.first().map(|x| x.as_str()) {
+        Some("run") => {
+            let part = get_number(args, "--part")?;
+            let input = get_value(args, "--input")?;
+            let all = args.iter().any(|x| x == "--all");
+            if all && input.is_some() {
+                return Err("--all runs every day on its own input, it cannot take --input".into());
+            }
+
+            let days_to_run = match all {
+                true => days::solved_days(),
+                false => vec![get_number(args, "--day")?.ok_or("Missing --day or --all")?],
+            };
+
+            let format = get_value(args, "--format")?.unwrap_or("text");
+            let log = args.iter().any(|x| x == "--log");
+            if format != "text" && format != "json" {
+                return Err(format!("Unknown format: {}", format).into());
+            }
+
+            let commit = match log {
+                true => history::current_commit(),
+                false => String::new(),
+            };
+            let mut failed = false;
+            for day in days_to_run {
+                let reports = report::run_day(day, part, input);
+                if reports.is_empty() {
+                    return match part {
+                        Some(part) => Err(format!("Day {} part {} is not solved", day, part).into()),
+                        None => Err(format!("Day {} is not solved", day).into()),
+                    };
+                }
+
+                for report in reports.iter() {
+                    failed |= report.result.is_err();
+                    match format {
+                        "json" => println!("{}", report.to_json()),
+                        _ => println!("{}", report.to_text()),
+                    }
+                }
+
+                if log {
+                    let timestamp = history::now();
+                    let entries = reports.iter()
+                        .filter_map(|x| Entry::from_report(x, timestamp, &commit))
+                        .collect::<Vec<Entry>>();
+                    history::append(Path::new(RESULTS_LOG), &entries)?;
+                }
+            }
+
+            if failed {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Some("history") => {
+            let day = get_number(args, "--day")?.ok_or("Missing --day")?;
+            let part = get_number(args, "--part")?;
+            let lines = history::history(&history::read(Path::new(RESULTS_LOG))?, day, part);
+            if lines.is_empty() {
+                return Err(format!("No logged answer for day {} in {}", day, RESULTS_LOG).into());
+            }
+
+            println!("Day {}", day);
+            for line in lines {
+                println!("{}", line);
+            }
+        }
+        Some("render") => {
+            let day = get_number(args, "--day")?.ok_or("Missing --day")?;
+            if day != 3 {
+                return Err(format!("Day {} has no renderer", day).into());
+            }
+
+            let path = get_value(args, "--input")?.map_or(report::input_path(day), |x| x.to_string());
+            let input = report::read_input(&path)?;
+            println!("{}", days::day_3::render(&input, io::stdout().is_terminal()));
+        }
+        Some("new") => {
+            let day = get_number(args, "--day")?.ok_or("Missing --day")?;
+            scaffold::new_day(Path::new("."), day)?;
+        }
+        Some(command) => return Err(format!("Unknown command: {}", command).into()),
+        None => return Err("Missing command".into()),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// The value following `flag` in the arguments, if the flag is there.
+fn get_value<'a>(args: &'a [String], flag: &str) -> Result<Option<&'a str>, Box<dyn Error>> {
+    let Some(index) = args.iter().position(|x| x == flag) else {
+        return Ok(None);
+    };
+
+    Ok(Some(args.get(index + 1).ok_or(format!("Missing value after {}", flag))?))
+}
+
+/// The number following `flag` in the arguments, if the flag is there.
+fn get_number(args: &[String], flag: &str) -> Result<Option<u32>, Box<dyn Error>> {
+    let Some(value) = get_value(args, flag)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(value.parse::<u32>().map_err(|_| format!("{} must be a number, got {}", flag, value))?))
+}
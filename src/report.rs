@@ -0,0 +1,238 @@
+use std::cell::Cell;
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use crate::days::solutions::{self, timed_together, Answer, SOLUTIONS};
+use crate::days::{StreamedSolver, STREAMED_PARTS};
+use crate::utils::checksum::{checksum, format_checksum, ChecksumReader};
+use crate::utils::parse::open_input;
+
+/// One part run on one input, as printed by `aoc run --format json`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub checksum: Option<String>,
+    pub result: Result<Answer, String>,
+}
+
+impl Report {
+    /// The report as a single line of text.
+    pub fn to_text(&self) -> String {
+        match &self.result {
+            Ok(answer) => {
+                let duration = answer.parse.unwrap_or_default() + answer.solve;
+                format!("Day {} part {}: {} ({:.3} ms)", self.day, self.part, answer.value, duration.as_secs_f64() * 1000.0)
+            }
+            Err(error) => format!("Day {} part {}: error: {}", self.day, self.part, error),
+        }
+    }
+
+    /// The report as a single line of JSON.
+    pub fn to_json(&self) -> String {
+        let mut fields = vec![
+            format!("\"day\":{}", self.day),
+            format!("\"part\":{}", self.part),
+            format!("\"input\":{}", json_string(&self.input)),
+            format!("\"checksum\":{}", self.checksum.as_deref().map_or("null".to_string(), json_string)),
+        ];
+
+        match &self.result {
+            Ok(answer) => {
+                fields.push(format!("\"answer\":{}", json_string(&answer.value)));
+                fields.push(format!("\"parse_ms\":{}", answer.parse.map_or("null".to_string(), |x| format!("{:.3}", x.as_secs_f64() * 1000.0))));
+                fields.push(format!("\"solve_ms\":{:.3}", answer.solve.as_secs_f64() * 1000.0));
+            }
+            Err(error) => fields.push(format!("\"error\":{}", json_string(error))),
+        }
+
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+/// The default input of a day.
+pub fn input_path(day: u32) -> String {
+    format!("src/inputs/day_{}.txt", day)
+}
+
+/// Reports one part of a day, or every part of the day when no part is given, on the input at `path`
+/// (the standard input for `-`) or on the day's own input. Parts that stream their input read it line by line;
+/// the others share a single read of the whole input. The standard input can only be read once, so it is read whole
+/// when several parts need it.
+pub fn run_day(day: u32, part: Option<u32>, path: Option<&str>) -> Vec<Report> {
+    let path = path.map_or(input_path(day), |x| x.to_string());
+    let parts = SOLUTIONS
+        .iter()
+        .filter(|(d, p, _)| *d == day && part.is_none_or(|x| x == *p))
+        .map(|(_, part, _)| *part)
+        .collect::<Vec<u32>>();
+
+    let mut input: Option<Result<String, String>> = None;
+    let mut reports: Vec<Report> = Vec::new();
+    for part in parts.iter().copied() {
+        let streamed = STREAMED_PARTS.iter().find(|(d, p, _)| *d == day && *p == part);
+        let (checksum, result) = match streamed {
+            Some((_, _, solver)) if path != "-" || parts.len() == 1 => stream(*solver, &path),
+            _ => match input.get_or_insert_with(|| read_input(&path)) {
+                Ok(input) => (Some(checksum(input)), catch_panic(|| solutions::solve(day, part, input))),
+                Err(error) => (None, Err(error.clone())),
+            },
+        };
+
+        reports.push(Report {
+            day,
+            part,
+            input: path.clone(),
+            checksum,
+            result,
+        });
+    }
+
+    reports
+}
+
+/// Solves a part while reading its input line by line, and gives the checksum of the input along with the answer.
+fn stream(solver: StreamedSolver, path: &str) -> (Option<String>, Result<Answer, String>) {
+    let reader = match open_input(path) {
+        Ok(reader) => reader,
+        Err(error) => return (None, Err(format!("Cannot read {}: {}", path, error))),
+    };
+
+    let (reader, hash) = ChecksumReader::new(reader);
+    let result = catch_panic(|| timed_together(|| solver(Box::new(reader)).map_err(|x| x.to_string())));
+
+    (hash.get().map(format_checksum), result)
+}
+
+/// The input at `path`, or the standard input for `-`.
+pub fn read_input(path: &str) -> Result<String, String> {
+    let mut input = String::new();
+    match path {
+        "-" => io::stdin().read_to_string(&mut input).map(|_| input),
+        _ => std::fs::read_to_string(path),
+    }
+    .map_err(|x| format!("Cannot read {}: {}", path, x))
+}
+
+thread_local! {
+    /// Whether `catch_panic` is running a solver on this thread.
+    static CATCHING_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
+static QUIET_PANIC_HOOK: Once = Once::new();
+
+/// Runs the solver, turning its panic into an error so the other parts still run. The panic hook stays quiet for the
+/// panics caught here, as they are reported with the part, and prints the other ones as before.
+fn catch_panic(solve: impl FnOnce() -> Result<Answer, String>) -> Result<Answer, String> {
+    QUIET_PANIC_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING_PANIC.with(|x| x.get()) {
+                hook(info);
+            }
+        }));
+    });
+
+    CATCHING_PANIC.with(|x| x.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(solve));
+    CATCHING_PANIC.with(|x| x.set(false));
+
+    result.unwrap_or_else(|payload| {
+        let message = payload.downcast_ref::<&str>().map(|x| x.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or("unknown panic".to_string());
+        Err(format!("Solver panicked: {}", message))
+    })
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            x if x.is_control() => escaped.push_str(&format!("\\u{:04x}", x as u32)),
+            x => escaped.push(x),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_to_json() {
+        let report = Report {
+            day: 6,
+            part: 2,
+            input: "src/inputs/day_6_test.txt".to_string(),
+            checksum: Some("00ff".to_string()),
+            result: Ok(Answer { value: "71503".to_string(), parse: Some(Duration::from_micros(1500)), solve: Duration::from_millis(2) }),
+        };
+
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":6,"part":2,"input":"src/inputs/day_6_test.txt","checksum":"00ff","answer":"71503","parse_ms":1.500,"solve_ms":2.000}"#
+        );
+    }
+
+    #[test]
+    fn test_to_json_error() {
+        let report = Report {
+            day: 1,
+            part: 1,
+            input: "missing.txt".to_string(),
+            checksum: None,
+            result: Err("Cannot read \"missing.txt\"".to_string()),
+        };
+
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":1,"part":1,"input":"missing.txt","checksum":null,"error":"Cannot read \"missing.txt\""}"#
+        );
+    }
+
+    #[test]
+    fn test_run_day() {
+        let reports = run_day(6, None, Some("src/inputs/day_6_test.txt"));
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].result.as_ref().unwrap().value, "288");
+        assert_eq!(reports[1].result.as_ref().unwrap().value, "71503");
+        assert_eq!(reports[0].checksum, Some(checksum(include_str!("../src/inputs/day_6_test.txt"))));
+        assert!(run_day(6, Some(1), Some("missing.txt"))[0].result.is_err());
+        assert!(run_day(26, None, None).is_empty());
+    }
+
+    #[test]
+    fn test_run_day_streamed_and_whole() {
+        // Day 4 part 1 streams its input, day 13 reads it whole
+        for (day, path, answer) in [(4, "src/inputs/day_4_test.txt", "13"), (13, "src/inputs/day_13_test.txt", "405")] {
+            let report = &run_day(day, Some(1), Some(path))[0];
+
+            assert_eq!(report.result.as_ref().unwrap().value, answer);
+            assert_eq!(report.checksum, Some(checksum(&std::fs::read_to_string(path).unwrap())));
+        }
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| panic!("Error: no answer")), Err("Solver panicked: Error: no answer".to_string()));
+        assert_eq!(catch_panic(|| Err("no answer".to_string())), Err("no answer".to_string()));
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), r#""a\"b\\c\nd\u0001""#);
+    }
+}
@@ -0,0 +1,225 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+const DAY_PLACEHOLDER: &str = "__DAY__";
+
+const TEMPLATE: &str = r#"#![allow(dead_code)]
+
+fn get_input(test_input: bool) -> &'static str {
+    match test_input {
+        true => include_str!("../../src/inputs/day___DAY___test.txt"),
+        false => include_str!("../../src/inputs/day___DAY__.txt")
+    }
+}
+
+pub fn solve_part_1(input: &str) -> u64 {
+    todo!("solve day __DAY__ part 1 for {} lines", input.lines().count())
+}
+
+pub fn solve_part_2(input: &str) -> u64 {
+    todo!("solve day __DAY__ part 2 for {} lines", input.lines().count())
+}
+
+pub fn day___DAY___part_1() {
+    println!("Part 1: {}", solve_part_1(get_input(false)));
+}
+
+pub fn day___DAY___part_2() {
+    println!("Part 2: {}", solve_part_2(get_input(false)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Replace with the answer given for the example in the puzzle
+    const EXAMPLE_ANSWER_PART_1: u64 = 0;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(solve_part_1(get_input(true)), EXAMPLE_ANSWER_PART_1);
+    }
+}
+"#;
+
+/// Creates the module and the empty inputs of a new day under `root`, and registers the module and its solutions.
+pub fn new_day(root: &Path, day: u32) -> Result<(), Box<dyn Error>> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Day must be between 1 and 25, got {}", day).into());
+    }
+
+    let module_path = root.join(format!("src/days/day_{}.rs", day));
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()).into());
+    }
+
+    let registry_path = root.join("src/days/mod.rs");
+    let registry = register_day(&fs::read_to_string(&registry_path)?, day)?;
+    let solutions_path = root.join("src/days/solutions.rs");
+    let solutions = register_solutions(&fs::read_to_string(&solutions_path)?, day)?;
+
+    for input in [format!("day_{}.txt", day), format!("day_{}_test.txt", day)] {
+        let input_path = root.join("src/inputs").join(input);
+        if !input_path.exists() {
+            fs::write(&input_path, "")?;
+            println!("Created {}", input_path.display());
+        }
+    }
+
+    fs::write(&module_path, TEMPLATE.replace(DAY_PLACEHOLDER, &day.to_string()))?;
+    println!("Created {}", module_path.display());
+
+    fs::write(&registry_path, registry)?;
+    fs::write(&solutions_path, solutions)?;
+    println!("Registered day {} in {} and {}", day, registry_path.display(), solutions_path.display());
+
+    Ok(())
+}
+
+/// Adds the `mod` declaration of the day, in order.
+fn register_day(registry: &str, day: u32) -> Result<String, Box<dyn Error>> {
+    let declaration = format!("pub mod day_{};", day);
+    if registry.lines().any(|x| x.trim() == declaration) {
+        return Err(format!("Day {} is already registered", day).into());
+    }
+
+    let mut lines = registry.lines().map(|x| x.to_string()).collect::<Vec<String>>();
+
+    // Keep the declarations sorted by day
+    let declarations = lines.iter()
+        .enumerate()
+        .filter_map(|(i, x)| {
+            let number = x.strip_prefix("pub mod day_")?.strip_suffix(';')?.parse::<u32>().ok()?;
+            Some((i, number))
+        })
+        .collect::<Vec<(usize, u32)>>();
+    let position = declarations.iter()
+        .find(|(_, number)| *number > day)
+        .map(|(i, _)| *i)
+        .or(declarations.last().map(|(i, _)| i + 1))
+        .unwrap_or(0);
+    lines.insert(position, declaration);
+
+    Ok(join_lines(lines, registry))
+}
+
+/// Adds the two parts of the day at the end of `SOLUTIONS`.
+fn register_solutions(solutions: &str, day: u32) -> Result<String, Box<dyn Error>> {
+    let mut lines = solutions.lines().map(|x| x.to_string()).collect::<Vec<String>>();
+
+    let solutions_start = lines.iter()
+        .position(|x| x.starts_with("pub const SOLUTIONS"))
+        .ok_or("Solutions have no SOLUTIONS list")?;
+    let solutions_end = lines[solutions_start..].iter()
+        .position(|x| x.trim() == "];")
+        .map(|x| x + solutions_start)
+        .ok_or("SOLUTIONS list is not closed")?;
+    for part in [2, 1] {
+        lines.insert(solutions_end, format!("    ({day}, {part}, |x| timed_together(|| Ok(day_{day}::solve_part_{part}(x)))),"));
+    }
+
+    Ok(join_lines(lines, solutions))
+}
+
+/// The lines joined back, ending with a newline if the original text did.
+fn join_lines(lines: Vec<String>, original: &str) -> String {
+    let mut text = lines.join("\n");
+    if original.ends_with('\n') {
+        text.push('\n');
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "pub mod day_1;
+pub mod day_3;
+pub mod solutions;
+
+pub fn solved_days() {}
+";
+
+    const SOLUTIONS: &str = "pub const SOLUTIONS: &[(u32, u32, Solver)] = &[
+    (1, 1, |x| timed_together(|| Ok(day_1::solve(x)))),
+];
+
+pub fn solve() {}
+";
+
+    #[test]
+    fn test_register_day() {
+        let registry = register_day(REGISTRY, 2).unwrap();
+
+        assert_eq!(registry, "pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod solutions;
+
+pub fn solved_days() {}
+");
+    }
+
+    #[test]
+    fn test_register_solutions() {
+        let solutions = register_solutions(SOLUTIONS, 2).unwrap();
+
+        assert_eq!(solutions, "pub const SOLUTIONS: &[(u32, u32, Solver)] = &[
+    (1, 1, |x| timed_together(|| Ok(day_1::solve(x)))),
+    (2, 1, |x| timed_together(|| Ok(day_2::solve_part_1(x)))),
+    (2, 2, |x| timed_together(|| Ok(day_2::solve_part_2(x)))),
+];
+
+pub fn solve() {}
+");
+        assert!(register_solutions("pub fn solve() {}", 2).is_err());
+    }
+
+    #[test]
+    fn test_register_last_day() {
+        let registry = register_day(REGISTRY, 25).unwrap();
+
+        assert!(registry.starts_with("pub mod day_1;\npub mod day_3;\npub mod day_25;\n"));
+    }
+
+    #[test]
+    fn test_register_existing_day() {
+        assert!(register_day(REGISTRY, 3).is_err());
+    }
+
+    #[test]
+    fn test_template() {
+        let module = TEMPLATE.replace(DAY_PLACEHOLDER, "7");
+
+        assert!(module.contains("include_str!(\"../../src/inputs/day_7_test.txt\")"));
+        assert!(module.contains("include_str!(\"../../src/inputs/day_7.txt\")"));
+        assert!(module.contains("pub fn day_7_part_1()"));
+        assert!(module.contains("pub fn day_7_part_2()"));
+        assert!(module.contains("pub fn solve_part_1(input: &str) -> u64"));
+        assert!(!module.contains(DAY_PLACEHOLDER));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::create_dir_all(root.join("src/inputs")).unwrap();
+        fs::write(root.join("src/days/mod.rs"), REGISTRY).unwrap();
+        fs::write(root.join("src/days/solutions.rs"), SOLUTIONS).unwrap();
+
+        new_day(&root, 7).unwrap();
+
+        assert!(fs::read_to_string(root.join("src/days/day_7.rs")).unwrap().contains("pub fn day_7_part_1()"));
+        assert_eq!(fs::read_to_string(root.join("src/inputs/day_7.txt")).unwrap(), "");
+        assert_eq!(fs::read_to_string(root.join("src/inputs/day_7_test.txt")).unwrap(), "");
+        assert!(fs::read_to_string(root.join("src/days/mod.rs")).unwrap().contains("pub mod day_7;"));
+        assert!(fs::read_to_string(root.join("src/days/solutions.rs")).unwrap().contains("day_7::solve_part_2(x)"));
+        assert!(new_day(&root, 7).is_err());
+        assert!(new_day(&root, 26).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
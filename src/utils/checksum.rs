@@ -0,0 +1,117 @@
+#![allow(dead_code)]
+
+use std::cell::Cell;
+use std::io::{self, BufRead, Read};
+use std::rc::Rc;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// The 64-bit FNV-1a hash of the bytes: not cryptographic, but enough to tell two inputs apart.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    fnv1a_extend(FNV_OFFSET_BASIS, bytes)
+}
+
+/// The FNV-1a hash of the bytes hashed to `hash` followed by `bytes`.
+pub fn fnv1a_extend(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| (hash ^ *byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+/// The checksum of an input, as 16 hexadecimal digits.
+pub fn checksum(input: &str) -> String {
+    format_checksum(fnv1a(input.as_bytes()))
+}
+
+pub fn format_checksum(hash: u64) -> String {
+    format!("{:016x}", hash)
+}
+
+/// Hashes the bytes read through it. Once dropped, it reads and hashes what is left of the input, so the hash covers
+/// the whole input even when its reader stopped early, and leaves it in the cell given by `new`. The cell stays empty
+/// if the input could not be read to the end.
+pub struct ChecksumReader<R: BufRead> {
+    inner: R,
+    hash: u64,
+    result: Rc<Cell<Option<u64>>>,
+}
+
+impl<R: BufRead> ChecksumReader<R> {
+    pub fn new(inner: R) -> (ChecksumReader<R>, Rc<Cell<Option<u64>>>) {
+        let result = Rc::new(Cell::new(None));
+        let reader = ChecksumReader {
+            inner,
+            hash: FNV_OFFSET_BASIS,
+            result: result.clone(),
+        };
+
+        (reader, result)
+    }
+}
+
+impl<R: BufRead> Read for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let length = available.len().min(buf.len());
+        buf[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Ok(length)
+    }
+}
+
+impl<R: BufRead> BufRead for ChecksumReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The bytes consumed are still in the buffer filled before, so no reading happens here
+        if let Ok(buffer) = self.inner.fill_buf() {
+            self.hash = fnv1a_extend(self.hash, &buffer[..amt.min(buffer.len())]);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+impl<R: BufRead> Drop for ChecksumReader<R> {
+    fn drop(&mut self) {
+        loop {
+            let length = match self.inner.fill_buf() {
+                Ok([]) => break,
+                Ok(buffer) => buffer.len(),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => return,
+            };
+            self.consume(length);
+        }
+
+        self.result.set(Some(self.hash));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(checksum("foobar"), "85944171f73967e8");
+        assert_eq!(fnv1a_extend(fnv1a(b"foo"), b"bar"), fnv1a(b"foobar"));
+    }
+
+    #[test]
+    fn test_checksum_reader_hashes_whole_input() {
+        let input = "first line\nsecond line\nthird line\n";
+        let (mut reader, result) = ChecksumReader::new(io::BufReader::with_capacity(4, input.as_bytes()));
+
+        let mut first_line = String::new();
+        reader.read_line(&mut first_line).unwrap();
+        assert_eq!(first_line, "first line\n");
+        assert_eq!(result.get(), None);
+
+        drop(reader);
+        assert_eq!(result.get().map(format_checksum), Some(checksum(input)));
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A loop found in a simulation: the state at `start + length` is the same as the one at `start`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps any index, however far, to the index already seen that holds the same state.
+    pub fn equivalent_index(&self, index: usize) -> usize {
+        if index < self.start {
+            return index;
+        }
+
+        self.start + (index - self.start) % self.length
+    }
+}
+
+/// Remembers the index at which each state was first seen, to detect when a simulation starts looping.
+#[derive(Debug)]
+pub struct CycleDetector<S: Hash + Eq> {
+    seen: HashMap<S, usize>,
+}
+
+impl<S: Hash + Eq> Default for CycleDetector<S> {
+    fn default() -> Self {
+        CycleDetector::new()
+    }
+}
+
+impl<S: Hash + Eq> CycleDetector<S> {
+    pub fn new() -> CycleDetector<S> {
+        CycleDetector {
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Records the state reached at `index`, and returns the cycle if that state was already seen.
+    pub fn record(&mut self, state: S, index: usize) -> Option<Cycle> {
+        match self.seen.get(&state) {
+            Some(&start) => Some(Cycle {
+                start,
+                length: index - start,
+            }),
+            None => {
+                self.seen.insert(state, index);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut detector = CycleDetector::new();
+
+        // 0, 1, 2, 3, 1, 2, 3, ...
+        assert_eq!(detector.record(0, 0), None);
+        assert_eq!(detector.record(1, 1), None);
+        assert_eq!(detector.record(2, 2), None);
+        assert_eq!(detector.record(3, 3), None);
+        assert_eq!(detector.record(1, 4), Some(Cycle { start: 1, length: 3 }));
+    }
+
+    #[test]
+    fn test_equivalent_index() {
+        let cycle = Cycle { start: 1, length: 3 };
+
+        assert_eq!(cycle.equivalent_index(0), 0);
+        assert_eq!(cycle.equivalent_index(3), 3);
+        assert_eq!(cycle.equivalent_index(4), 1);
+        assert_eq!(cycle.equivalent_index(1_000_000_000), 1);
+    }
+}
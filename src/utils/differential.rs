@@ -0,0 +1,153 @@
+#![allow(dead_code)]
+
+use std::fmt::{self, Debug, Display};
+use std::ops::Range;
+
+/// An input on which two solvers disagree, shrunk until no smaller input still makes them disagree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch<I, O> {
+    pub seed: u64,
+    pub input: I,
+    pub expected: O,
+    pub actual: O,
+    pub shrinks: usize,
+}
+
+impl<I: Debug, O: Debug> Display for Mismatch<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Solvers disagree on the input generated from seed {}, after {} shrinks:", self.seed, self.shrinks)?;
+        writeln!(f, "{:#?}", self.input)?;
+        writeln!(f, "Expected (reference): {:?}", self.expected)?;
+        write!(f, "Actual (candidate):   {:?}", self.actual)
+    }
+}
+
+/// Runs the reference and the candidate solver on the input generated from every seed, and stops at the
+/// first disagreement. The failing input is then replaced by the first smaller input from `shrink` on which
+/// the solvers still disagree, until none does.
+pub fn compare<I, O, G, S, R, C>(seeds: Range<u64>, generate: G, shrink: S, reference: R, candidate: C) -> Result<(), Mismatch<I, O>>
+where
+    O: PartialEq,
+    G: Fn(u64) -> I,
+    S: Fn(&I) -> Vec<I>,
+    R: Fn(&I) -> O,
+    C: Fn(&I) -> O,
+{
+    let disagree = |input: &I| {
+        let (expected, actual) = (reference(input), candidate(input));
+        (expected != actual).then_some((expected, actual))
+    };
+
+    for seed in seeds {
+        let input = generate(seed);
+        let Some((expected, actual)) = disagree(&input) else {
+            continue;
+        };
+
+        let mut mismatch = Mismatch { seed, input, expected, actual, shrinks: 0 };
+        while let Some((input, (expected, actual))) = shrink(&mismatch.input).into_iter().find_map(|x| disagree(&x).map(|y| (x, y))) {
+            mismatch = Mismatch { seed, input, expected, actual, shrinks: mismatch.shrinks + 1 };
+        }
+
+        return Err(mismatch);
+    }
+
+    Ok(())
+}
+
+/// Smaller numbers to try in place of `number`, the smallest first.
+pub fn shrink_number(number: u64) -> Vec<u64> {
+    let mut smaller = vec![0, number / 2, number.saturating_sub(1)];
+    smaller.retain(|x| *x < number);
+    smaller.dedup();
+
+    smaller
+}
+
+/// The values without their second half, then without each value in turn.
+pub fn shrink_vec<T: Clone>(values: &[T]) -> Vec<Vec<T>> {
+    let mut smaller: Vec<Vec<T>> = Vec::new();
+    if values.len() > 1 {
+        smaller.push(values[..values.len() / 2].to_vec());
+    }
+    for i in 0..values.len() {
+        let mut without = values.to_vec();
+        without.remove(i);
+        smaller.push(without);
+    }
+
+    smaller
+}
+
+/// The input without each of the lines for which `removable` holds, in turn.
+pub fn shrink_lines<F: Fn(&str) -> bool>(input: &str, removable: F) -> Vec<String> {
+    let lines = input.lines().collect::<Vec<&str>>();
+
+    (0..lines.len())
+        .filter(|i| removable(lines[*i]))
+        .map(|i| {
+            let mut without = lines.clone();
+            without.remove(i);
+            without.join("\n")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(seed: u64) -> Vec<u64> {
+        (0..10).map(|x| (x * 37 + seed * 11) % 100).collect()
+    }
+
+    #[test]
+    fn test_compare_agreeing_solvers() {
+        let sum = |x: &Vec<u64>| x.iter().sum::<u64>();
+        let sum_by_hand = |x: &Vec<u64>| {
+            let mut total = 0;
+            for value in x {
+                total += value;
+            }
+            total
+        };
+
+        assert_eq!(compare(0..100, generate, |x| shrink_vec(x), sum, sum_by_hand), Ok(()));
+    }
+
+    #[test]
+    fn test_compare_shrinks_mismatch() {
+        let sum = |x: &Vec<u64>| x.iter().sum::<u64>();
+        let sum_small = |x: &Vec<u64>| x.iter().filter(|x| **x <= 50).sum::<u64>();
+        let shrink = |x: &Vec<u64>| {
+            let mut smaller = shrink_vec(x);
+            for (i, value) in x.iter().enumerate() {
+                for number in shrink_number(*value) {
+                    let mut other = x.clone();
+                    other[i] = number;
+                    smaller.push(other);
+                }
+            }
+            smaller
+        };
+
+        let mismatch = compare(0..100, generate, shrink, sum, sum_small).unwrap_err();
+
+        assert_eq!(mismatch.seed, 0);
+        assert_eq!(mismatch.input, vec![51]);
+        assert_eq!((mismatch.expected, mismatch.actual), (51, 0));
+        assert!(mismatch.to_string().contains("seed 0"));
+    }
+
+    #[test]
+    fn test_shrink_number() {
+        assert_eq!(shrink_number(10), vec![0, 5, 9]);
+        assert_eq!(shrink_number(1), vec![0]);
+        assert!(shrink_number(0).is_empty());
+    }
+
+    #[test]
+    fn test_shrink_lines() {
+        assert_eq!(shrink_lines("a\nb\nc", |x| x != "b"), vec!["b\nc", "a\nb"]);
+    }
+}
@@ -0,0 +1,259 @@
+#![allow(dead_code)]
+
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// A weighted graph with nodes numbered from 0, stored as the list of outgoing edges of each node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph {
+    pub edges: Vec<Vec<(usize, u64)>>,
+}
+
+impl Graph {
+    pub fn new(nb_nodes: usize) -> Graph {
+        Graph {
+            edges: vec![Vec::new(); nb_nodes],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn add_node(&mut self) -> usize {
+        self.edges.push(Vec::new());
+        self.edges.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: u64) {
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, weight: u64) {
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+
+    pub fn neighbours(&self, node: usize) -> &[(usize, u64)] {
+        &self.edges[node]
+    }
+
+    /// The global minimum cut of an undirected graph with the Stoer-Wagner algorithm: the total weight of
+    /// the cheapest set of edges splitting the graph in two, with the nodes of one of the two sides.
+    pub fn minimum_cut(&self) -> Option<(u64, Vec<usize>)> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        // Merged nodes keep the sum of the weights of their edges, and the original nodes they contain
+        let mut weights: Vec<HashMap<usize, u64>> = vec![HashMap::new(); self.len()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for (to, weight) in edges {
+                if from != *to {
+                    *weights[from].entry(*to).or_insert(0) += weight;
+                }
+            }
+        }
+        let mut groups: Vec<Vec<usize>> = (0..self.len()).map(|x| vec![x]).collect();
+        let mut active: Vec<usize> = (0..self.len()).collect();
+        let mut best: Option<(u64, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            // Add the most tightly connected node to the set until all are in, remembering the last two
+            let mut connection: HashMap<usize, u64> = active.iter().map(|x| (*x, 0)).collect();
+            let mut in_set: HashSet<usize> = HashSet::new();
+            let mut heap: BinaryHeap<(u64, usize)> = BinaryHeap::from([(0, active[0])]);
+            let (mut previous, mut last) = (active[0], active[0]);
+
+            while in_set.len() < active.len() {
+                // Disconnected nodes are never pushed, carry on from one of them
+                let Some((weight, node)) = heap.pop() else {
+                    let next = *active.iter().find(|x| !in_set.contains(x)).unwrap();
+                    heap.push((connection[&next], next));
+                    continue;
+                };
+                if in_set.contains(&node) || weight != connection[&node] {
+                    continue;
+                }
+                in_set.insert(node);
+                previous = last;
+                last = node;
+
+                for (next, edge) in weights[node].iter() {
+                    if !in_set.contains(next) {
+                        let value = connection.get_mut(next).unwrap();
+                        *value += edge;
+                        heap.push((*value, *next));
+                    }
+                }
+            }
+
+            let cut = connection[&last];
+            if best.as_ref().is_none_or(|(weight, _)| cut < *weight) {
+                best = Some((cut, groups[last].clone()));
+            }
+
+            // Merge the last node into the one added before it
+            let last_weights = std::mem::take(&mut weights[last]);
+            for (next, edge) in last_weights {
+                weights[next].remove(&last);
+                if next != previous {
+                    *weights[previous].entry(next).or_insert(0) += edge;
+                    *weights[next].entry(previous).or_insert(0) += edge;
+                }
+            }
+            let last_group = std::mem::take(&mut groups[last]);
+            groups[previous].extend(last_group);
+            active.retain(|x| *x != last);
+        }
+
+        best
+    }
+}
+
+/// Compresses a grid of corridors into a graph of its junctions, weighted by the length of the corridors.
+///
+/// `moves` lists the cells that can be reached in one step from a cell. The junctions are the `keep` cells
+/// and every cell with more than two moves; the corridors between them become edges, in the direction
+/// they can be walked. Returns the graph with the cell of each node.
+pub fn compress_grid<F>(keep: &[(usize, usize)], moves: F) -> (Graph, Vec<(usize, usize)>)
+where
+    F: Fn((usize, usize)) -> Vec<(usize, usize)>,
+{
+    let mut cells: Vec<(usize, usize)> = keep.to_vec();
+    let mut nodes: HashMap<(usize, usize), usize> = cells.iter().enumerate().map(|(i, x)| (*x, i)).collect();
+
+    // Find every junction reachable from the kept cells
+    let mut seen: HashSet<(usize, usize)> = keep.iter().copied().collect();
+    let mut stack: Vec<(usize, usize)> = keep.to_vec();
+    while let Some(cell) = stack.pop() {
+        let next_cells = moves(cell);
+        if next_cells.len() > 2 && !nodes.contains_key(&cell) {
+            nodes.insert(cell, cells.len());
+            cells.push(cell);
+        }
+
+        for next in next_cells {
+            if seen.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+
+    // Walk every corridor leaving a junction until the next junction
+    let mut graph = Graph::new(cells.len());
+    for (from, start) in cells.iter().enumerate() {
+        for first in moves(*start) {
+            let (mut previous, mut current, mut length) = (*start, first, 1);
+
+            while !nodes.contains_key(&current) {
+                let next = moves(current).into_iter().find(|x| *x != previous);
+                match next {
+                    Some(next) => {
+                        previous = current;
+                        current = next;
+                        length += 1;
+                    }
+                    None => break,
+                }
+            }
+
+            if let Some(&to) = nodes.get(&current) {
+                if to != from {
+                    graph.add_edge(from, to, length);
+                }
+            }
+        }
+    }
+
+    (graph, cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_moves<'a>(grid: &'a [&'a str]) -> impl Fn((usize, usize)) -> Vec<(usize, usize)> + 'a {
+        move |(x, y)| {
+            let mut moves = Vec::new();
+            let candidates = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+            for (next_x, next_y) in candidates {
+                if grid.get(next_y).and_then(|row| row.as_bytes().get(next_x)) == Some(&b'.') {
+                    moves.push((next_x, next_y));
+                }
+            }
+            moves
+        }
+    }
+
+    #[test]
+    fn test_graph() {
+        let mut graph = Graph::new(2);
+        let node = graph.add_node();
+        graph.add_edge(0, 1, 3);
+        graph.add_undirected_edge(1, node, 5);
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.neighbours(0), &[(1, 3)]);
+        assert_eq!(graph.neighbours(1), &[(2, 5)]);
+        assert_eq!(graph.neighbours(2), &[(1, 5)]);
+    }
+
+    #[test]
+    fn test_minimum_cut() {
+        // Two groups of 4 nodes all linked together, joined by the edges 3-4 and 1-5
+        let mut graph = Graph::new(8);
+        for (a, b) in [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3), (4, 5), (4, 6), (4, 7), (5, 6), (5, 7), (6, 7)] {
+            graph.add_undirected_edge(a, b, 1);
+        }
+        graph.add_undirected_edge(3, 4, 1);
+        graph.add_undirected_edge(1, 5, 1);
+
+        let (weight, mut side) = graph.minimum_cut().unwrap();
+        side.sort();
+
+        assert_eq!(weight, 2);
+        assert!(side == vec![0, 1, 2, 3] || side == vec![4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_minimum_cut_disconnected() {
+        let mut graph = Graph::new(3);
+        graph.add_undirected_edge(0, 1, 4);
+
+        assert_eq!(graph.minimum_cut().map(|x| x.0), Some(0));
+        assert_eq!(Graph::new(1).minimum_cut(), None);
+    }
+
+    #[test]
+    fn test_compress_grid() {
+        /*
+            #.#####
+            #.....#
+            #.###.#
+            #.....#
+            #####.#
+        */
+        let grid = ["#.#####", "#.....#", "#.###.#", "#.....#", "#####.#"];
+        let (graph, cells) = compress_grid(&[(1, 0), (5, 4)], grid_moves(&grid));
+
+        // The two loops meet at (1, 1) and (5, 3), the only cells with three ways out
+        assert_eq!(cells.len(), 4);
+        let junction_a = cells.iter().position(|x| *x == (1, 1)).unwrap();
+        let junction_b = cells.iter().position(|x| *x == (5, 3)).unwrap();
+
+        assert_eq!(graph.neighbours(0), &[(junction_a, 1)]);
+        assert_eq!(graph.neighbours(1), &[(junction_b, 1)]);
+
+        let mut lengths = graph.neighbours(junction_a)
+            .iter()
+            .filter(|(to, _)| *to == junction_b)
+            .map(|(_, length)| *length)
+            .collect::<Vec<u64>>();
+        lengths.sort();
+        assert_eq!(lengths, vec![6, 6]);
+    }
+}
@@ -0,0 +1,10 @@
+pub mod parse;
+pub mod cycle;
+pub mod search;
+pub mod polygon;
+pub mod range;
+pub mod vector;
+pub mod graph;
+pub mod random;
+pub mod differential;
+pub mod checksum;
@@ -0,0 +1,82 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+
+/// Splits the input into blocks separated by blank lines, whatever the line endings (`\n` or `\r\n`).
+/// Each block is trimmed of its surrounding line breaks, and empty blocks are skipped.
+pub fn split_blocks(input: &str) -> Vec<&str> {
+    let mut blocks: Vec<&str> = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(block_start) = start.take() {
+                blocks.push(&input[block_start..end]);
+            }
+        } else {
+            if start.is_none() {
+                start = Some(offset);
+            }
+            end = offset + line.trim_end().len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(block_start) = start {
+        blocks.push(&input[block_start..end]);
+    }
+
+    blocks
+}
+
+/// Reads the non-blank lines of `reader` one at a time, so only the current line is held in memory.
+/// Line endings (`\n` or `\r\n`) are removed.
+pub fn read_records<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<String>> {
+    reader.lines().filter(|x| !matches!(x, Ok(line) if line.trim().is_empty()))
+}
+
+/// The error for a line that is not in the expected format.
+pub fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Opens an input for buffered reading: a file, or the standard input for `-`.
+pub fn open_input(path: &str) -> io::Result<Box<dyn BufRead>> {
+    match path {
+        "-" => Ok(Box::new(io::stdin().lock())),
+        _ => Ok(Box::new(BufReader::new(File::open(path)?))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_blocks_lf() {
+        let blocks = split_blocks("a\nb\n\nc\n\n\nd\n");
+
+        assert_eq!(blocks, vec!["a\nb", "c", "d"]);
+    }
+
+    #[test]
+    fn test_split_blocks_crlf() {
+        let blocks = split_blocks("a\r\nb\r\n\r\nc\r\n");
+
+        assert_eq!(blocks, vec!["a\r\nb", "c"]);
+    }
+
+    #[test]
+    fn test_split_blocks_empty() {
+        assert!(split_blocks("").is_empty());
+        assert!(split_blocks("\n\r\n  \n").is_empty());
+    }
+
+    #[test]
+    fn test_read_records() {
+        let records = read_records("a\r\n\nb\n  \nc".as_bytes()).collect::<io::Result<Vec<String>>>().unwrap();
+
+        assert_eq!(records, vec!["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,53 @@
+/// Area of a simple polygon with the shoelace formula. The vertices can be in either winding order.
+pub fn shoelace_area(vertices: &[(i64, i64)]) -> i64 {
+    let twice_area: i64 = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+        .sum();
+
+    twice_area.abs() / 2
+}
+
+/// Number of lattice points on the edges of a polygon whose edges are all horizontal or vertical.
+pub fn boundary_points(vertices: &[(i64, i64)]) -> i64 {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|((x1, y1), (x2, y2))| (x2 - x1).abs() + (y2 - y1).abs())
+        .sum()
+}
+
+/// Number of lattice points strictly inside the polygon, with Pick's theorem: `A = I + B / 2 - 1`.
+pub fn interior_points(vertices: &[(i64, i64)]) -> i64 {
+    shoelace_area(vertices) - boundary_points(vertices) / 2 + 1
+}
+
+/// Number of lattice points inside or on the edges of the polygon.
+pub fn enclosed_points(vertices: &[(i64, i64)]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square() {
+        let square = [(0, 0), (3, 0), (3, 3), (0, 3)];
+
+        assert_eq!(shoelace_area(&square), 9);
+        assert_eq!(boundary_points(&square), 12);
+        assert_eq!(interior_points(&square), 4);
+        assert_eq!(enclosed_points(&square), 16);
+    }
+
+    #[test]
+    fn test_winding_order() {
+        let clockwise = [(0, 0), (0, 2), (4, 2), (4, 0)];
+        let counter_clockwise = [(0, 0), (4, 0), (4, 2), (0, 2)];
+
+        assert_eq!(shoelace_area(&clockwise), 8);
+        assert_eq!(shoelace_area(&counter_clockwise), 8);
+    }
+}
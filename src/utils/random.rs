@@ -0,0 +1,87 @@
+#![allow(dead_code)]
+
+use std::ops::Range;
+
+/// A small seeded pseudo-random generator (SplitMix64), so generated inputs can be replayed from their seed.
+#[derive(Debug, Clone)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random {
+            state: seed,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "Error: cannot pick a number in an empty range");
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// True once every `n` times on average.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.range(0..n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, values: &'a [T]) -> &'a T {
+        &values[self.range(0..values.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            let j = self.range(0..i as u64 + 1) as usize;
+            values.swap(i, j);
+        }
+    }
+
+    /// `count` distinct numbers in `range`, in random order.
+    pub fn distinct(&mut self, range: Range<u64>, count: usize) -> Vec<u64> {
+        let mut values = range.collect::<Vec<u64>>();
+        assert!(count <= values.len(), "Error: cannot pick {} distinct numbers among {}", count, values.len());
+        self.shuffle(&mut values);
+        values.truncate(count);
+
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_numbers() {
+        let mut first = Random::new(42);
+        let mut second = Random::new(42);
+
+        assert_eq!((0..10).map(|_| first.next_u64()).collect::<Vec<u64>>(), (0..10).map(|_| second.next_u64()).collect::<Vec<u64>>());
+        assert_ne!(Random::new(1).next_u64(), Random::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_range() {
+        let mut random = Random::new(7);
+
+        assert!((0..1000).map(|_| random.range(5..8)).all(|x| (5..8).contains(&x)));
+    }
+
+    #[test]
+    fn test_distinct() {
+        let mut random = Random::new(7);
+        let mut values = random.distinct(0..20, 20);
+        values.sort();
+
+        assert_eq!(values, (0..20).collect::<Vec<u64>>());
+    }
+}
@@ -0,0 +1,59 @@
+#![allow(dead_code)]
+
+use std::ops::Range;
+
+/// Splits a half-open range into the part strictly below `at` and the part from `at` onwards.
+pub fn split_at(range: &Range<u64>, at: u64) -> (Option<Range<u64>>, Option<Range<u64>>) {
+    let at = at.clamp(range.start, range.end);
+    let below = range.start..at;
+    let above = at..range.end;
+
+    (
+        (!below.is_empty()).then_some(below),
+        (!above.is_empty()).then_some(above),
+    )
+}
+
+/// The part of `range` that is also in `other`, if any.
+pub fn intersect(range: &Range<u64>, other: &Range<u64>) -> Option<Range<u64>> {
+    let intersection = range.start.max(other.start)..range.end.min(other.end);
+
+    (!intersection.is_empty()).then_some(intersection)
+}
+
+/// The parts of `range` that are not in `other`: at most one below it and one above it.
+pub fn subtract(range: &Range<u64>, other: &Range<u64>) -> Vec<Range<u64>> {
+    let (below, rest) = split_at(range, other.start);
+    let above = rest.and_then(|x| split_at(&x, other.end).1);
+
+    below.into_iter().chain(above).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_at() {
+        assert_eq!(split_at(&(1..10), 5), (Some(1..5), Some(5..10)));
+        assert_eq!(split_at(&(1..10), 1), (None, Some(1..10)));
+        assert_eq!(split_at(&(1..10), 10), (Some(1..10), None));
+        assert_eq!(split_at(&(1..10), 0), (None, Some(1..10)));
+        assert_eq!(split_at(&(1..10), 20), (Some(1..10), None));
+    }
+
+    #[test]
+    fn test_intersect() {
+        assert_eq!(intersect(&(1..10), &(5..20)), Some(5..10));
+        assert_eq!(intersect(&(1..10), &(3..4)), Some(3..4));
+        assert_eq!(intersect(&(1..10), &(10..20)), None);
+    }
+
+    #[test]
+    fn test_subtract() {
+        assert_eq!(subtract(&(1..10), &(3..5)), vec![1..3, 5..10]);
+        assert_eq!(subtract(&(1..10), &(0..5)), vec![5..10]);
+        assert_eq!(subtract(&(1..10), &(0..20)), vec![]);
+        assert_eq!(subtract(&(1..10), &(20..30)), vec![1..10]);
+    }
+}
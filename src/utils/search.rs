@@ -0,0 +1,112 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// An entry of the open set, ordered so that the `BinaryHeap` pops the lowest estimate first.
+struct Candidate<S> {
+    estimate: u64,
+    cost: u64,
+    state: S,
+}
+
+impl<S> PartialEq for Candidate<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate && self.cost == other.cost
+    }
+}
+
+impl<S> Eq for Candidate<S> {}
+
+impl<S> PartialOrd for Candidate<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Candidate<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate).then_with(|| other.cost.cmp(&self.cost))
+    }
+}
+
+/// Finds the cheapest way from any of the `starts` to a state accepted by `is_goal`, and returns that state
+/// with its cost. `neighbours` lists the states reachable from a state with the cost of each move, and
+/// `heuristic` must never overestimate the remaining cost for the result to be the cheapest.
+pub fn a_star<S, N, H, G>(starts: Vec<S>, mut neighbours: N, mut heuristic: H, mut is_goal: G) -> Option<(S, u64)>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> Vec<(S, u64)>,
+    H: FnMut(&S) -> u64,
+    G: FnMut(&S) -> bool,
+{
+    let mut best: HashMap<S, u64> = HashMap::new();
+    let mut open: BinaryHeap<Candidate<S>> = BinaryHeap::new();
+
+    for state in starts {
+        best.insert(state.clone(), 0);
+        open.push(Candidate { estimate: heuristic(&state), cost: 0, state });
+    }
+
+    while let Some(Candidate { cost, state, .. }) = open.pop() {
+        if is_goal(&state) {
+            return Some((state, cost));
+        }
+
+        // A cheaper way to this state was already expanded
+        if best.get(&state).is_some_and(|x| *x < cost) {
+            continue;
+        }
+
+        for (next, move_cost) in neighbours(&state) {
+            let next_cost = cost + move_cost;
+            if best.get(&next).is_some_and(|x| *x <= next_cost) {
+                continue;
+            }
+
+            best.insert(next.clone(), next_cost);
+            open.push(Candidate { estimate: next_cost + heuristic(&next), cost: next_cost, state: next });
+        }
+    }
+
+    None
+}
+
+/// A* without a heuristic.
+pub fn dijkstra<S, N, G>(starts: Vec<S>, neighbours: N, is_goal: G) -> Option<(S, u64)>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> Vec<(S, u64)>,
+    G: FnMut(&S) -> bool,
+{
+    a_star(starts, neighbours, |_| 0, is_goal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        0 --1--> 1 --1--> 2 --1--> 3
+        0 ------------5----------> 3
+    */
+    fn graph(state: &u32) -> Vec<(u32, u64)> {
+        match state {
+            0 => vec![(1, 1), (3, 5)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(dijkstra(vec![0], graph, |x| *x == 3), Some((3, 3)));
+        assert_eq!(dijkstra(vec![2], graph, |x| *x == 3), Some((3, 1)));
+        assert_eq!(dijkstra(vec![1], graph, |x| *x == 0), None);
+    }
+
+    #[test]
+    fn test_a_star() {
+        assert_eq!(a_star(vec![0], graph, |x| 3 - (*x as u64), |x| *x == 3), Some((3, 3)));
+    }
+}
@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+
+#[derive(Clone)]
+#[derive(PartialEq)]
+pub struct Vector {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vector3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl std::str::FromStr for Vector3 {
+    type Err = Box<dyn std::error::Error>;
+
+    /// Parses coordinates written as `x,y,z`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coordinates = s.split(',')
+            .map(|x| x.trim().parse::<i32>())
+            .collect::<Result<Vec<i32>, _>>()?;
+
+        match coordinates[..] {
+            [x, y, z] => Ok(Vector3 { x, y, z }),
+            _ => Err(format!("Vector must have 3 coordinates: {}", s).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vector3() {
+        assert_eq!("1,0,-2".parse::<Vector3>().unwrap(), Vector3 { x: 1, y: 0, z: -2 });
+        assert_eq!("1, 2, 3".parse::<Vector3>().unwrap(), Vector3 { x: 1, y: 2, z: 3 });
+        assert!("1,2".parse::<Vector3>().is_err());
+        assert!("1,2,a".parse::<Vector3>().is_err());
+    }
+}